@@ -87,6 +87,10 @@ impl Log for Logger {
     fn flush(&self) {
         let _ = io::stderr().flush();
     }
+
+    fn max_level(&self) -> LevelFilter {
+        self.filter.max_level()
+    }
 }
 
 /// Initializes the global logger, reading configuration from the `RUST_LOG` environment variable.
@@ -115,10 +119,12 @@ pub fn try_init() -> Result<(), SetLoggerError> {
         }
     }
 
-    let filter = builder.build();
-    let max_level = filter.max_level();
+    let logger = Logger {
+        filter: builder.build(),
+    };
+    let max_level = logger.max_level();
 
-    witchcraft_log::set_boxed_logger(Box::new(Logger { filter }))?;
+    witchcraft_log::set_boxed_logger(Box::new(logger))?;
     witchcraft_log::set_max_level(max_level);
 
     Ok(())
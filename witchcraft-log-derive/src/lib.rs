@@ -0,0 +1,123 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Derive macros for `witchcraft-log`.
+//!
+//! This crate should not be used directly. The macros are reexported by `witchcraft-log` when its `derive` Cargo
+//! feature is enabled.
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields};
+
+enum Safety {
+    Safe,
+    Unsafe,
+    Skip,
+}
+
+/// Derives `witchcraft_log::LogParams` for a struct with named fields.
+///
+/// See the documentation of `witchcraft_log::LogParams` for details.
+#[proc_macro_derive(LogParams, attributes(safe, r#unsafe, skip))]
+pub fn derive_log_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.ident.span(),
+                    "LogParams can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "LogParams can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut inserts = vec![];
+    let mut bounds = Vec::<syn::WherePredicate>::new();
+    for field in fields {
+        let (method, check, bound) = match safety(&field.attrs)? {
+            Safety::Safe => (
+                quote!(insert_safe),
+                quote!(safe_param),
+                quote!(::witchcraft_log::private::SafeParam),
+            ),
+            Safety::Unsafe => (
+                quote!(insert_unsafe),
+                quote!(unsafe_param),
+                quote!(::witchcraft_log::private::serde::Serialize),
+            ),
+            Safety::Skip => continue,
+        };
+
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let key = ident.to_string();
+        inserts.push(quote! {
+            params.#method(#key, ::witchcraft_log::private::#check(&self.#ident));
+        });
+        bounds.push(parse_quote!(#ty: #bound));
+    }
+
+    input.generics.make_where_clause().predicates.extend(bounds);
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::witchcraft_log::LogParams for #ident #ty_generics #where_clause {
+            fn log_params<'__a>(&'__a self, params: &mut ::witchcraft_log::Params<'__a>) {
+                #(#inserts)*
+            }
+        }
+    })
+}
+
+fn safety(attrs: &[syn::Attribute]) -> Result<Safety, Error> {
+    let mut safety = None;
+    for attr in attrs {
+        // `unsafe` is a keyword, so it has to be written as the raw identifier `r#unsafe`
+        let value = match attr.path().get_ident().map(|i| i.unraw().to_string()) {
+            Some(name) if name == "safe" => Safety::Safe,
+            Some(name) if name == "unsafe" => Safety::Unsafe,
+            Some(name) if name == "skip" => Safety::Skip,
+            _ => continue,
+        };
+
+        attr.meta.require_path_only()?;
+        if safety.replace(value).is_some() {
+            return Err(Error::new(
+                attr.span(),
+                "only one of #[safe], #[r#unsafe], or #[skip] may be specified",
+            ));
+        }
+    }
+
+    Ok(safety.unwrap_or(Safety::Unsafe))
+}
@@ -0,0 +1,553 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A logger which writes records on a background thread.
+
+use std::collections::VecDeque;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle, ThreadId};
+
+use conjure_object::Utc;
+use witchcraft_log::{LevelFilter, Log, Metadata, OwnedRecord, Record, mdc};
+
+/// The behavior of an [`AsyncLogger`] when its queue is full.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Block the logging thread until space is available in the queue.
+    Block,
+    /// Discard the record being logged.
+    DropNewest,
+    /// Discard the oldest record in the queue to make room for the record being logged.
+    ///
+    /// If every buffered record is already being processed by the worker thread, the record being logged is discarded
+    /// instead.
+    DropOldest,
+}
+
+/// A logger which forwards records to another logger on a background thread.
+///
+/// Records are converted to [`OwnedRecord`]s on the logging thread, capturing the MDC, timestamp, and thread if the
+/// record does not already contain them, and are then sent over a bounded queue to a worker thread. The behavior when
+/// the queue is full is determined by the logger's [`OverflowPolicy`].
+///
+/// Calls to [`Log::flush`] block until all previously logged records have been processed, and then flush the delegate
+/// logger. Dropping the logger processes all remaining records and shuts down the worker thread.
+///
+/// Panics raised by the delegate logger while processing a record are caught on the worker thread, so a single bad
+/// record does not stop the processing of the records after it.
+pub struct AsyncLogger<L> {
+    shared: Arc<Shared<L>>,
+    worker: Option<JoinHandle<()>>,
+    worker_id: ThreadId,
+}
+
+impl AsyncLogger<()> {
+    /// Returns a new builder.
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder {
+            capacity: 1024,
+            overflow_policy: OverflowPolicy::Block,
+            thread_name: "witchcraft-async-logger".to_string(),
+        }
+    }
+}
+
+impl<L> AsyncLogger<L>
+where
+    L: Log + 'static,
+{
+    /// Creates a new logger with the default configuration.
+    pub fn new(logger: L) -> Self {
+        AsyncLogger::builder().build(logger)
+    }
+
+    /// Returns a shared reference to the delegate logger.
+    #[inline]
+    pub fn get_ref(&self) -> &L {
+        &self.shared.logger
+    }
+
+    /// Returns the number of records which have been discarded due to the queue being full.
+    #[inline]
+    pub fn dropped_records(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    fn push(&self, record: OwnedRecord) {
+        let mut state = self.shared.lock();
+
+        if state.len() >= self.shared.capacity {
+            match self.shared.overflow_policy {
+                OverflowPolicy::Block => {
+                    while state.len() >= self.shared.capacity && !state.shutdown {
+                        state = self
+                            .shared
+                            .not_full
+                            .wait(state)
+                            .unwrap_or_else(|e| e.into_inner());
+                    }
+                }
+                OverflowPolicy::DropNewest => {
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                OverflowPolicy::DropOldest => {
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    if state.queue.pop_front().is_none() {
+                        return;
+                    }
+                    state.processed += 1;
+                }
+            }
+        }
+
+        state.queue.push_back(record);
+        state.pushed += 1;
+        self.shared.not_empty.notify_one();
+    }
+}
+
+impl<L> Log for AsyncLogger<L>
+where
+    L: Log + 'static,
+{
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.shared.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        // Logging from the worker thread would deadlock if the queue is full, so handle those records inline.
+        if thread::current().id() == self.worker_id {
+            self.shared.logger.log(record);
+            return;
+        }
+
+        self.push(capture(record));
+    }
+
+    fn flush(&self) {
+        if thread::current().id() != self.worker_id {
+            let mut state = self.shared.lock();
+            let target = state.pushed;
+            while state.processed < target {
+                state = self
+                    .shared
+                    .drained
+                    .wait(state)
+                    .unwrap_or_else(|e| e.into_inner());
+            }
+        }
+
+        self.shared.logger.flush();
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.shared.logger.max_level()
+    }
+}
+
+impl<L> Drop for AsyncLogger<L> {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.not_empty.notify_all();
+        self.shared.not_full.notify_all();
+
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// A builder for [`AsyncLogger`]s.
+pub struct Builder {
+    capacity: usize,
+    overflow_policy: OverflowPolicy,
+    thread_name: String,
+}
+
+impl Builder {
+    /// Sets the maximum number of records that can be buffered by the logger.
+    ///
+    /// This includes both records waiting in the queue and records currently being processed by the worker thread.
+    ///
+    /// Defaults to 1024.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    #[inline]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the behavior when the queue is full.
+    ///
+    /// Defaults to [`OverflowPolicy::Block`].
+    #[inline]
+    pub fn overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Sets the name of the worker thread.
+    ///
+    /// Defaults to `witchcraft-async-logger`.
+    #[inline]
+    pub fn thread_name(mut self, thread_name: &str) -> Self {
+        self.thread_name = thread_name.to_string();
+        self
+    }
+
+    /// Consumes the builder, returning a logger which forwards to the provided delegate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the worker thread cannot be spawned.
+    pub fn build<L>(self, logger: L) -> AsyncLogger<L>
+    where
+        L: Log + 'static,
+    {
+        let shared = Arc::new(Shared {
+            logger,
+            capacity: self.capacity,
+            overflow_policy: self.overflow_policy,
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                in_flight: 0,
+                pushed: 0,
+                processed: 0,
+                shutdown: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            drained: Condvar::new(),
+            dropped: AtomicU64::new(0),
+        });
+
+        let worker = thread::Builder::new()
+            .name(self.thread_name)
+            .spawn({
+                let shared = shared.clone();
+                move || worker(&shared)
+            })
+            .expect("failed to spawn async logger thread");
+
+        AsyncLogger {
+            shared,
+            worker_id: worker.thread().id(),
+            worker: Some(worker),
+        }
+    }
+}
+
+struct Shared<L> {
+    logger: L,
+    capacity: usize,
+    overflow_policy: OverflowPolicy,
+    state: Mutex<State>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    drained: Condvar,
+    dropped: AtomicU64,
+}
+
+impl<L> Shared<L> {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+struct State {
+    queue: VecDeque<OwnedRecord>,
+    // records taken off of the queue by the worker which are still being processed count against the capacity
+    in_flight: usize,
+    // records dropped from the front of the queue count as processed so flushes don't wait on them
+    pushed: u64,
+    processed: u64,
+    shutdown: bool,
+}
+
+impl State {
+    fn len(&self) -> usize {
+        self.queue.len() + self.in_flight
+    }
+}
+
+fn capture(record: &Record<'_>) -> OwnedRecord {
+    if record.timestamp().is_some() && record.thread_id().is_some() && record.mdc().is_some() {
+        return record.to_owned();
+    }
+
+    let thread = thread::current();
+    let mdc = mdc::snapshot();
+    let mut builder = record.to_builder();
+    if record.timestamp().is_none() {
+        builder.timestamp(Some(Utc::now()));
+    }
+    if record.thread_id().is_none() {
+        builder
+            .thread_name(thread.name())
+            .thread_id(Some(thread.id()));
+    }
+    if record.mdc().is_none() {
+        builder.mdc(Some(&mdc));
+    }
+    builder.build().to_owned()
+}
+
+fn worker<L>(shared: &Shared<L>)
+where
+    L: Log,
+{
+    let mut batch = VecDeque::new();
+
+    loop {
+        {
+            let mut state = shared.lock();
+            while state.queue.is_empty() && !state.shutdown {
+                state = shared
+                    .not_empty
+                    .wait(state)
+                    .unwrap_or_else(|e| e.into_inner());
+            }
+            if state.queue.is_empty() {
+                return;
+            }
+            mem::swap(&mut state.queue, &mut batch);
+            state.in_flight = batch.len();
+        }
+
+        let len = batch.len() as u64;
+        for record in batch.drain(..) {
+            // a panicking delegate would otherwise kill the worker and hang every flush and blocked producer
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+                record.as_record(|record| shared.logger.log(record))
+            }));
+        }
+
+        {
+            let mut state = shared.lock();
+            state.processed += len;
+            state.in_flight = 0;
+        }
+        shared.not_full.notify_all();
+        shared.drained.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc::{self, Receiver, Sender};
+
+    use witchcraft_log::Level;
+
+    use super::*;
+
+    struct TestLogger {
+        records: Mutex<Vec<OwnedRecord>>,
+        gate: Mutex<()>,
+        started: Mutex<Sender<()>>,
+    }
+
+    impl TestLogger {
+        fn new() -> (TestLogger, Receiver<()>) {
+            let (tx, rx) = mpsc::channel();
+            let logger = TestLogger {
+                records: Mutex::new(vec![]),
+                gate: Mutex::new(()),
+                started: Mutex::new(tx),
+            };
+            (logger, rx)
+        }
+
+        fn messages(&self) -> Vec<String> {
+            self.records
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|r| r.message().to_string())
+                .collect()
+        }
+    }
+
+    impl Log for TestLogger {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.level() <= Level::Info
+        }
+
+        fn log(&self, record: &Record<'_>) {
+            let _ = self.started.lock().unwrap().send(());
+            let _gate = self.gate.lock().unwrap();
+            self.records.lock().unwrap().push(record.to_owned());
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn log(logger: &dyn Log, level: Level, message: &'static str) {
+        logger.log(&Record::builder().level(level).message(message).build());
+    }
+
+    #[test]
+    fn captures_context() {
+        let (inner, _rx) = TestLogger::new();
+        let logger = AsyncLogger::new(inner);
+
+        let _guard = mdc::scope();
+        mdc::insert_safe("foo", "bar");
+        log(&logger, Level::Info, "hello");
+        log(&logger, Level::Debug, "filtered");
+        logger.flush();
+
+        let records = logger.get_ref().records.lock().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].message(), "hello");
+        assert!(records[0].timestamp().is_some());
+        assert_eq!(records[0].thread_id(), Some(thread::current().id()));
+        assert_eq!(records[0].mdc(), Some(&mdc::snapshot()));
+    }
+
+    fn overflow(policy: OverflowPolicy) -> Vec<String> {
+        let (inner, rx) = TestLogger::new();
+        let logger = AsyncLogger::builder()
+            .capacity(2)
+            .overflow_policy(policy)
+            .build(inner);
+
+        let gate = logger.get_ref().gate.lock().unwrap();
+        log(&logger, Level::Info, "a");
+        // wait for the worker to pick up the first record, which still counts against the capacity
+        rx.recv().unwrap();
+        log(&logger, Level::Info, "b");
+        log(&logger, Level::Info, "c");
+        drop(gate);
+
+        logger.flush();
+        assert_eq!(logger.dropped_records(), 1);
+        logger.get_ref().messages()
+    }
+
+    #[test]
+    fn drop_newest() {
+        assert_eq!(overflow(OverflowPolicy::DropNewest), ["a", "b"]);
+    }
+
+    #[test]
+    fn drop_oldest() {
+        assert_eq!(overflow(OverflowPolicy::DropOldest), ["a", "c"]);
+    }
+
+    #[test]
+    fn in_flight_counts_against_capacity() {
+        for policy in [OverflowPolicy::DropNewest, OverflowPolicy::DropOldest] {
+            let (inner, rx) = TestLogger::new();
+            let logger = AsyncLogger::builder()
+                .capacity(1)
+                .overflow_policy(policy)
+                .build(inner);
+
+            let gate = logger.get_ref().gate.lock().unwrap();
+            log(&logger, Level::Info, "a");
+            rx.recv().unwrap();
+            log(&logger, Level::Info, "b");
+            drop(gate);
+
+            logger.flush();
+            assert_eq!(logger.dropped_records(), 1);
+            assert_eq!(logger.get_ref().messages(), ["a"]);
+        }
+    }
+
+    #[test]
+    fn block() {
+        let (inner, _rx) = TestLogger::new();
+        let logger = AsyncLogger::builder().capacity(1).build(inner);
+
+        for _ in 0..100 {
+            log(&logger, Level::Info, "a");
+        }
+        logger.flush();
+
+        assert_eq!(logger.dropped_records(), 0);
+        assert_eq!(logger.get_ref().messages().len(), 100);
+    }
+
+    #[test]
+    fn drains_on_drop() {
+        let records = Arc::new(Mutex::new(vec![]));
+
+        struct ArcLogger(Arc<Mutex<Vec<OwnedRecord>>>);
+
+        impl Log for ArcLogger {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record<'_>) {
+                self.0.lock().unwrap().push(record.to_owned());
+            }
+
+            fn flush(&self) {}
+        }
+
+        let logger = AsyncLogger::new(ArcLogger(records.clone()));
+        for _ in 0..10 {
+            log(&logger, Level::Info, "a");
+        }
+        drop(logger);
+
+        assert_eq!(records.lock().unwrap().len(), 10);
+    }
+
+    #[test]
+    fn survives_panics() {
+        struct PanickingLogger(Mutex<Vec<String>>);
+
+        impl Log for PanickingLogger {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record<'_>) {
+                if record.message() == "panic" {
+                    panic!("bad record");
+                }
+                self.0.lock().unwrap().push(record.message().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        let logger = AsyncLogger::builder()
+            .capacity(1)
+            .build(PanickingLogger(Mutex::new(vec![])));
+        log(&logger, Level::Info, "a");
+        log(&logger, Level::Info, "panic");
+        for _ in 0..10 {
+            log(&logger, Level::Info, "b");
+        }
+        logger.flush();
+
+        assert_eq!(logger.get_ref().0.lock().unwrap().len(), 11);
+    }
+}
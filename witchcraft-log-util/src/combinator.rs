@@ -0,0 +1,297 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Loggers which wrap and combine other loggers.
+
+use witchcraft_log::{LevelFilter, Log, Metadata, Record, RecordBuilder};
+
+use crate::filter::Filter;
+
+/// A logger which dispatches records to multiple loggers.
+///
+/// A record is enabled if it is enabled by any of the child loggers, and is only logged to the children which enable
+/// it.
+#[derive(Default)]
+pub struct Tee {
+    loggers: Vec<Box<dyn Log>>,
+}
+
+impl Tee {
+    /// Creates a new logger with no children.
+    #[inline]
+    pub fn new() -> Self {
+        Tee::default()
+    }
+
+    /// Adds a child logger.
+    #[inline]
+    pub fn with<L>(mut self, logger: L) -> Self
+    where
+        L: Log + 'static,
+    {
+        self.loggers.push(Box::new(logger));
+        self
+    }
+}
+
+impl Log for Tee {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.loggers.iter().any(|l| l.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        for logger in &self.loggers {
+            if logger.enabled(record.metadata()) {
+                logger.log(record);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        for logger in &self.loggers {
+            logger.flush();
+        }
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.loggers
+            .iter()
+            .map(|l| l.max_level())
+            .max()
+            .unwrap_or(LevelFilter::Off)
+    }
+}
+
+/// A logger which applies a [`Filter`] before forwarding records to another logger.
+pub struct Filtered<L> {
+    filter: Filter,
+    logger: L,
+}
+
+impl<L> Filtered<L>
+where
+    L: Log,
+{
+    /// Creates a new logger which forwards records matching the filter to the provided logger.
+    #[inline]
+    pub fn new(filter: Filter, logger: L) -> Self {
+        Filtered { filter, logger }
+    }
+
+    /// Returns a shared reference to the filter.
+    #[inline]
+    pub fn filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    /// Returns a shared reference to the delegate logger.
+    #[inline]
+    pub fn get_ref(&self) -> &L {
+        &self.logger
+    }
+}
+
+impl<L> Log for Filtered<L>
+where
+    L: Log,
+{
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.filter.enabled(metadata) && self.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.filter.enabled(record.metadata()) {
+            self.logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.logger.flush();
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.filter.max_level().min(self.logger.max_level())
+    }
+}
+
+/// A logger which rewrites records before forwarding them to another logger.
+///
+/// The mapping function is passed the original record along with a [`RecordBuilder`] initialized to its values, and
+/// can modify the builder before the record is forwarded. It is also applied to records containing only a level and
+/// target when checking if metadata is enabled, so the metadata of the output record should only depend on the metadata
+/// of the input record.
+pub struct MapRecord<L, F> {
+    logger: L,
+    map: F,
+}
+
+impl<L, F> MapRecord<L, F>
+where
+    L: Log,
+    F: for<'a> Fn(&Record<'a>, &mut RecordBuilder<'a>) + Sync + Send,
+{
+    /// Creates a new logger which applies the mapping function to records before forwarding them to the provided
+    /// logger.
+    #[inline]
+    pub fn new(logger: L, map: F) -> Self {
+        MapRecord { logger, map }
+    }
+
+    /// Returns a shared reference to the delegate logger.
+    #[inline]
+    pub fn get_ref(&self) -> &L {
+        &self.logger
+    }
+}
+
+impl<L, F> Log for MapRecord<L, F>
+where
+    L: Log,
+    F: for<'a> Fn(&Record<'a>, &mut RecordBuilder<'a>) + Sync + Send,
+{
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        let record = Record::builder()
+            .level(metadata.level())
+            .target(metadata.target())
+            .build();
+        let mut builder = record.to_builder();
+        (self.map)(&record, &mut builder);
+        self.logger.enabled(builder.build().metadata())
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let mut builder = record.to_builder();
+        (self.map)(record, &mut builder);
+        self.logger.log(&builder.build());
+    }
+
+    fn flush(&self) {
+        self.logger.flush();
+    }
+
+    // the mapping function may change record levels, so we can't rely on the delegate's max level
+    fn max_level(&self) -> LevelFilter {
+        LevelFilter::Trace
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use witchcraft_log::Level;
+
+    use super::*;
+    use crate::test_util::{TestLogger, log};
+
+    fn metadata(level: Level, target: &str) -> Metadata<'_> {
+        Metadata::builder().level(level).target(target).build()
+    }
+
+    #[test]
+    fn tee() {
+        let warn = TestLogger::new(LevelFilter::Warn);
+        let debug = TestLogger::new(LevelFilter::Debug);
+        let tee = Tee::new().with(warn.clone()).with(debug.clone());
+
+        assert!(tee.enabled(&metadata(Level::Debug, "foo")));
+        assert!(!tee.enabled(&metadata(Level::Trace, "foo")));
+        assert_eq!(tee.max_level(), LevelFilter::Debug);
+        assert_eq!(Tee::new().max_level(), LevelFilter::Off);
+
+        log(&tee, Level::Error, "foo", "error");
+        log(&tee, Level::Info, "foo", "info");
+        assert_eq!(warn.messages(), ["error"]);
+        assert_eq!(debug.messages(), ["error", "info"]);
+    }
+
+    #[test]
+    fn tee_checks_children() {
+        // a logger which only filters in `enabled`
+        #[derive(Clone)]
+        struct Unfiltered {
+            level: LevelFilter,
+            messages: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl Log for Unfiltered {
+            fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+                metadata.level() <= self.level
+            }
+
+            fn log(&self, record: &Record<'_>) {
+                self.messages
+                    .lock()
+                    .unwrap()
+                    .push(record.message().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        let warn = Unfiltered {
+            level: LevelFilter::Warn,
+            messages: Arc::default(),
+        };
+        let debug = Unfiltered {
+            level: LevelFilter::Debug,
+            messages: Arc::default(),
+        };
+        let tee = Tee::new().with(warn.clone()).with(debug.clone());
+
+        log(&tee, Level::Error, "foo", "error");
+        log(&tee, Level::Info, "foo", "info");
+        assert_eq!(*warn.messages.lock().unwrap(), ["error"]);
+        assert_eq!(*debug.messages.lock().unwrap(), ["error", "info"]);
+    }
+
+    #[test]
+    fn filtered() {
+        let inner = TestLogger::new(LevelFilter::Info);
+        let filter = Filter::builder()
+            .level(LevelFilter::Warn)
+            .target_level("foo", LevelFilter::Trace)
+            .build();
+        let filtered = Filtered::new(filter, inner.clone());
+
+        assert!(filtered.enabled(&metadata(Level::Info, "foo")));
+        assert!(!filtered.enabled(&metadata(Level::Debug, "foo")));
+        assert!(!filtered.enabled(&metadata(Level::Info, "bar")));
+        assert_eq!(filtered.max_level(), LevelFilter::Info);
+
+        log(&filtered, Level::Info, "foo", "foo");
+        log(&filtered, Level::Info, "bar", "bar");
+        log(&filtered, Level::Warn, "bar", "bar warn");
+        assert_eq!(inner.messages(), ["foo", "bar warn"]);
+    }
+
+    #[test]
+    fn map_record() {
+        let inner = TestLogger::new(LevelFilter::Info);
+        let mapped = MapRecord::new(
+            inner.clone(),
+            |record: &Record<'_>, builder: &mut RecordBuilder<'_>| {
+                if record.target().starts_with("noisy") && record.level() == Level::Info {
+                    builder.level(Level::Debug);
+                }
+            },
+        );
+
+        assert!(mapped.enabled(&metadata(Level::Info, "quiet")));
+        assert!(!mapped.enabled(&metadata(Level::Info, "noisy")));
+
+        log(&mapped, Level::Info, "noisy", "noisy");
+        log(&mapped, Level::Info, "quiet", "quiet");
+        assert_eq!(inner.messages(), ["quiet"]);
+    }
+}
@@ -14,6 +14,12 @@
 //! Utilities for Witchcraft logger implementations.
 #![warn(missing_docs)]
 
+pub mod async_logger;
+pub mod combinator;
 pub mod filter;
 pub mod mdc;
+pub mod reload;
 pub mod service;
+pub mod span;
+#[cfg(test)]
+mod test_util;
@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 //! MDC keys with special behavior.
+//!
+//! Each key is available both as a raw name and as a typed [`Key`], which should be preferred when reading or writing
+//! the MDC.
+use witchcraft_log::mdc::Key;
+use witchcraft_logging_api::objects::{OrganizationId, SessionId, TokenId, TraceId, UserId};
 
 /// The safe MDC key storing the value for the `uid` field in service logs.
 pub const UID_KEY: &str = "\0witchcraft-uid";
@@ -27,3 +32,18 @@ pub const ORG_ID_KEY: &str = "\0witchcraft-org-id";
 
 /// The safe MDC key storing the value for the `traceId` field in service logs.
 pub const TRACE_ID_KEY: &str = "\0witchcraft-trace-id";
+
+/// The typed MDC key storing the value for the `uid` field in service logs.
+pub const UID: Key<UserId> = Key::assume_safe(UID_KEY);
+
+/// The typed MDC key storing the value for the `sid` field in service logs.
+pub const SID: Key<SessionId> = Key::assume_safe(SID_KEY);
+
+/// The typed MDC key storing the value for the `tokenId` field in service logs.
+pub const TOKEN_ID: Key<TokenId> = Key::assume_safe(TOKEN_ID_KEY);
+
+/// The typed MDC key storing the value for the `orgId` field in service logs.
+pub const ORG_ID: Key<OrganizationId> = Key::assume_safe(ORG_ID_KEY);
+
+/// The typed MDC key storing the value for the `traceId` field in service logs.
+pub const TRACE_ID: Key<TraceId> = Key::assume_safe(TRACE_ID_KEY);
@@ -0,0 +1,280 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A logger whose configuration can be changed at runtime.
+//!
+//! The global logger can only be set once, so this module provides a [`ReloadableLogger`] which forwards records
+//! matching a [`Filter`] to a delegate logger, along with a [`Handle`] which can atomically replace the filter and
+//! delegate while the logger is in use.
+//!
+//! # Examples
+//!
+//! ```
+//! use witchcraft_log::{LevelFilter, Log, Metadata, Record};
+//! use witchcraft_log_util::filter::Filter;
+//! use witchcraft_log_util::reload;
+//! # struct MyWitchcraftLogger;
+//! # impl Log for MyWitchcraftLogger {
+//! #    fn enabled(&self, _: &Metadata<'_>) -> bool { true }
+//! #    fn log(&self, _: &Record<'_>) {}
+//! #    fn flush(&self) {}
+//! # }
+//!
+//! let filter = Filter::builder().level(LevelFilter::Info).build();
+//! let handle = reload::init(filter, MyWitchcraftLogger).unwrap();
+//!
+//! // later, turn on debug logging for a noisy module
+//! let filter = Filter::builder()
+//!     .level(LevelFilter::Info)
+//!     .target_level("my_service::noisy", LevelFilter::Debug)
+//!     .build();
+//! handle.set_filter(filter);
+//! ```
+
+use std::mem;
+use std::sync::{Arc, RwLock};
+
+use witchcraft_log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::filter::Filter;
+
+struct Inner {
+    filter: Arc<Filter>,
+    logger: Arc<dyn Log>,
+}
+
+impl Inner {
+    fn max_level(&self) -> LevelFilter {
+        self.filter.max_level().min(self.logger.max_level())
+    }
+}
+
+type Shared = Arc<RwLock<Arc<Inner>>>;
+
+fn load(shared: &Shared) -> Arc<Inner> {
+    shared.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// A logger which applies a reloadable [`Filter`] before forwarding records to a reloadable delegate logger.
+///
+/// The logger's configuration is changed through the [`Handle`] returned along with it.
+pub struct ReloadableLogger {
+    shared: Shared,
+}
+
+impl ReloadableLogger {
+    /// Creates a new logger which forwards records matching the filter to the provided logger, along with a handle
+    /// which can be used to reconfigure it.
+    ///
+    /// The handle does not update the global max levels, so this should be used when the logger will not be
+    /// installed directly as the global logger. See [`init`] otherwise.
+    pub fn new<L>(filter: Filter, logger: L) -> (Self, Handle)
+    where
+        L: Log + 'static,
+    {
+        let shared = Arc::new(RwLock::new(Arc::new(Inner {
+            filter: Arc::new(filter),
+            logger: Arc::new(logger),
+        })));
+
+        let logger = ReloadableLogger {
+            shared: shared.clone(),
+        };
+        let handle = Handle {
+            shared,
+            global: false,
+        };
+
+        (logger, handle)
+    }
+}
+
+impl Log for ReloadableLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        let inner = load(&self.shared);
+        inner.filter.enabled(metadata) && inner.logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        // the lock is not held while logging so reloads don't block on, or deadlock with, the delegate
+        let inner = load(&self.shared);
+        if inner.filter.enabled(record.metadata()) {
+            inner.logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        load(&self.shared).logger.flush();
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        load(&self.shared).max_level()
+    }
+}
+
+/// A handle used to reconfigure a [`ReloadableLogger`].
+#[derive(Clone)]
+pub struct Handle {
+    shared: Shared,
+    global: bool,
+}
+
+impl Handle {
+    /// Atomically replaces both the filter and the delegate logger.
+    ///
+    /// The previous delegate logger is flushed.
+    pub fn reload<L>(&self, filter: Filter, logger: L)
+    where
+        L: Log + 'static,
+    {
+        self.update(|_| Inner {
+            filter: Arc::new(filter),
+            logger: Arc::new(logger),
+        });
+    }
+
+    /// Replaces the filter.
+    pub fn set_filter(&self, filter: Filter) {
+        self.update(|inner| Inner {
+            filter: Arc::new(filter),
+            logger: inner.logger.clone(),
+        });
+    }
+
+    /// Replaces the delegate logger.
+    ///
+    /// The previous delegate logger is flushed.
+    pub fn set_logger<L>(&self, logger: L)
+    where
+        L: Log + 'static,
+    {
+        self.update(|inner| Inner {
+            filter: inner.filter.clone(),
+            logger: Arc::new(logger),
+        });
+    }
+
+    /// Returns the current most verbose level of the logger.
+    pub fn max_level(&self) -> LevelFilter {
+        load(&self.shared).max_level()
+    }
+
+    fn update<F>(&self, f: F)
+    where
+        F: FnOnce(&Inner) -> Inner,
+    {
+        let (old, replaced_logger) = {
+            let mut current = self.shared.write().unwrap_or_else(|e| e.into_inner());
+            let new = Arc::new(f(&current));
+            let max_level = new.max_level();
+            let replaced_logger = !Arc::ptr_eq(&current.logger, &new.logger);
+            let old = mem::replace(&mut *current, new);
+
+            // update the global state while holding the lock so concurrent reloads apply in order
+            if self.global {
+                witchcraft_log::set_max_level(max_level);
+            } else {
+                witchcraft_log::rebuild_interest_cache();
+            }
+
+            (old, replaced_logger)
+        };
+
+        if replaced_logger {
+            old.logger.flush();
+        }
+    }
+}
+
+/// Installs a [`ReloadableLogger`] as the global logger, returning a handle which can be used to reconfigure it.
+///
+/// The global max level is set from the logger's configuration, and is updated whenever it is reconfigured through
+/// the handle. If the `log` crate bridge was installed with `witchcraft_log::bridge::init`, the `log` crate's max level
+/// follows along automatically.
+///
+/// Returns an error if the global logger has already been set.
+pub fn init<L>(filter: Filter, logger: L) -> Result<Handle, SetLoggerError>
+where
+    L: Log + 'static,
+{
+    let (logger, mut handle) = ReloadableLogger::new(filter, logger);
+    let max_level = logger.max_level();
+
+    witchcraft_log::set_boxed_logger(Box::new(logger))?;
+    witchcraft_log::set_max_level(max_level);
+
+    handle.global = true;
+    Ok(handle)
+}
+
+#[cfg(test)]
+mod test {
+    use witchcraft_log::Level;
+
+    use super::*;
+    use crate::test_util::{TestLogger, log};
+
+    #[test]
+    fn reload() {
+        let first = TestLogger::new(LevelFilter::Trace);
+        let (logger, handle) = ReloadableLogger::new(
+            Filter::builder().level(LevelFilter::Info).build(),
+            first.clone(),
+        );
+        assert_eq!(logger.max_level(), LevelFilter::Info);
+
+        log(&logger, Level::Info, "foo", "info");
+        log(&logger, Level::Debug, "foo", "debug");
+        assert_eq!(first.messages(), ["info"]);
+
+        handle.set_filter(
+            Filter::builder()
+                .level(LevelFilter::Info)
+                .target_level("foo", LevelFilter::Debug)
+                .build(),
+        );
+        assert_eq!(handle.max_level(), LevelFilter::Debug);
+        assert!(
+            logger.enabled(
+                &Metadata::builder()
+                    .level(Level::Debug)
+                    .target("foo::bar")
+                    .build()
+            )
+        );
+        log(&logger, Level::Debug, "foo", "debug");
+        assert_eq!(first.messages(), ["info", "debug"]);
+        assert_eq!(first.flushes(), 0);
+
+        let second = TestLogger::new(LevelFilter::Warn);
+        handle.set_logger(second.clone());
+        assert_eq!(first.flushes(), 1);
+        assert_eq!(logger.max_level(), LevelFilter::Warn);
+
+        log(&logger, Level::Debug, "foo", "debug");
+        log(&logger, Level::Warn, "foo", "warn");
+        assert_eq!(first.messages(), ["info", "debug"]);
+        assert_eq!(second.messages(), ["warn"]);
+
+        let third = TestLogger::new(LevelFilter::Trace);
+        handle.reload(
+            Filter::builder().level(LevelFilter::Off).build(),
+            third.clone(),
+        );
+        assert_eq!(second.flushes(), 1);
+        assert_eq!(logger.max_level(), LevelFilter::Off);
+
+        log(&logger, Level::Fatal, "foo", "fatal");
+        assert!(third.messages().is_empty());
+    }
+}
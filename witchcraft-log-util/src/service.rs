@@ -13,13 +13,14 @@
 // limitations under the License.
 //! Utilities for Witchcraft service logs.
 use std::fmt::Write;
-use std::{error, thread};
+use std::thread;
 
-use conjure_error::ErrorKind;
 use conjure_object::Utc;
 use witchcraft_log::{Level, Record, mdc};
-use witchcraft_logging_api::objects::{
-    LogLevel, OrganizationId, ServiceLogV1, SessionId, TokenId, TraceId, UserId,
+use witchcraft_logging_api::objects::{LogLevel, ServiceLogV1};
+
+use crate::mdc::{
+    ORG_ID, ORG_ID_KEY, SID, SID_KEY, TOKEN_ID, TOKEN_ID_KEY, TRACE_ID, TRACE_ID_KEY, UID, UID_KEY,
 };
 
 /// Serialize a `witchcraft-log` record into a standard `ServiceLogV1` object.
@@ -33,43 +34,47 @@ pub fn from_record(record: &Record<'_>) -> ServiceLogV1 {
         Level::Trace => LogLevel::Trace,
     };
 
+    // Prefer the context captured when the record was created, since we may be running on a different thread.
+    let thread = match record.thread_id() {
+        Some(_) => record.thread_name().map(ToString::to_string),
+        None => thread::current().name().map(ToString::to_string),
+    };
+
     let mut message = ServiceLogV1::builder()
         .type_("service.1")
         .level(level)
-        .time(Utc::now())
+        .time(record.timestamp().unwrap_or_else(Utc::now))
         .message(record.message())
         .safe(true)
         .origin(record.target().to_string())
-        .thread(thread::current().name().map(ToString::to_string));
+        .thread(thread);
 
-    let mdc = mdc::snapshot();
+    let current_mdc;
+    let mdc = match record.mdc() {
+        Some(mdc) => mdc,
+        None => {
+            current_mdc = mdc::snapshot();
+            &current_mdc
+        }
+    };
+    if let Some(uid) = mdc.get(UID) {
+        message = message.uid(uid);
+    }
+    if let Some(sid) = mdc.get(SID) {
+        message = message.sid(sid);
+    }
+    if let Some(token_id) = mdc.get(TOKEN_ID) {
+        message = message.token_id(token_id);
+    }
+    if let Some(org_id) = mdc.get(ORG_ID) {
+        message = message.org_id(org_id);
+    }
+    if let Some(trace_id) = mdc.get(TRACE_ID) {
+        message = message.trace_id(trace_id);
+    }
     for (key, value) in mdc.safe().iter() {
         match key {
-            crate::mdc::UID_KEY => {
-                if let Ok(uid) = value.clone().deserialize_into::<UserId>() {
-                    message = message.uid(uid);
-                }
-            }
-            crate::mdc::SID_KEY => {
-                if let Ok(sid) = value.clone().deserialize_into::<SessionId>() {
-                    message = message.sid(sid);
-                }
-            }
-            crate::mdc::TOKEN_ID_KEY => {
-                if let Ok(token_id) = value.clone().deserialize_into::<TokenId>() {
-                    message = message.token_id(token_id);
-                }
-            }
-            crate::mdc::ORG_ID_KEY => {
-                if let Ok(org_id) = value.clone().deserialize_into::<OrganizationId>() {
-                    message = message.org_id(org_id);
-                }
-            }
-            crate::mdc::TRACE_ID_KEY => {
-                if let Ok(trace_id) = value.clone().deserialize_into::<TraceId>() {
-                    message = message.trace_id(trace_id);
-                }
-            }
+            UID_KEY | SID_KEY | TOKEN_ID_KEY | ORG_ID_KEY | TRACE_ID_KEY => {}
             key => message = message.insert_params(key, value),
         }
     }
@@ -78,6 +83,11 @@ pub fn from_record(record: &Record<'_>) -> ServiceLogV1 {
             .iter()
             .map(|(k, v)| (k.to_string(), v.clone())),
     );
+    message = message.extend_tags(
+        mdc.tags()
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string())),
+    );
 
     if let Some(file) = record.file() {
         message = message.insert_params("file", file);
@@ -86,7 +96,7 @@ pub fn from_record(record: &Record<'_>) -> ServiceLogV1 {
         message = message.insert_params("line", line);
     }
     if let Some(error) = record.error() {
-        if let ErrorKind::Service(s) = error.kind() {
+        if let Some(s) = error.service_error() {
             message = message
                 .insert_params("errorInstanceId", s.error_instance_id())
                 .insert_params("errorCode", s.error_code())
@@ -95,25 +105,22 @@ pub fn from_record(record: &Record<'_>) -> ServiceLogV1 {
 
         let mut stacktrace = String::new();
         for trace in error.backtraces() {
-            writeln!(stacktrace, "{trace:?}").unwrap();
+            writeln!(stacktrace, "{trace}").unwrap();
         }
         message = message.stacktrace(stacktrace);
 
-        let mut causes = vec![];
-        let mut cause = Some(error.cause() as &dyn error::Error);
-        while let Some(e) = cause {
-            causes.push(e.to_string());
-            cause = e.source();
-        }
-        if error.cause_safe() {
-            message = message.insert_params("errorCause", causes);
-        } else {
-            message = message.insert_unsafe_params("errorCause", causes);
+        let causes = error.causes();
+        if !causes.is_empty() {
+            if error.cause_safe() {
+                message = message.insert_params("errorCause", causes);
+            } else {
+                message = message.insert_unsafe_params("errorCause", causes);
+            }
         }
-        for (key, value) in &error.safe_params() {
+        for (key, value) in error.safe_params() {
             message = message.insert_params(key, value);
         }
-        for (key, value) in &error.unsafe_params() {
+        for (key, value) in error.unsafe_params() {
             message = message.insert_unsafe_params(key, value);
         }
     }
@@ -123,6 +130,147 @@ pub fn from_record(record: &Record<'_>) -> ServiceLogV1 {
     for (key, value) in record.unsafe_params() {
         message = message.insert_unsafe_params(*key, value);
     }
+    for (key, value) in record.tags() {
+        message = message.insert_tags(*key, *value);
+    }
 
     message.build()
 }
+
+#[cfg(test)]
+mod test {
+    use std::{error, fmt, io};
+
+    use conjure_error::Error;
+    use conjure_object::{Any, DateTime, Utc};
+    use witchcraft_log::mdc::Snapshot;
+    use witchcraft_logging_api::objects::{TraceId, UserId};
+
+    use super::*;
+
+    #[test]
+    fn captured_context() {
+        let _guard = mdc::scope();
+        mdc::insert_safe("current", "value");
+
+        let timestamp = DateTime::<Utc>::from_timestamp(1_000_000, 0).unwrap();
+        let mut snapshot = Snapshot::new();
+        snapshot.safe_mut().insert("captured", "value");
+        let thread_id = thread::spawn(|| thread::current().id()).join().unwrap();
+
+        let log = from_record(
+            &Record::builder()
+                .timestamp(Some(timestamp))
+                .thread_name(Some("captured-thread"))
+                .thread_id(Some(thread_id))
+                .mdc(Some(&snapshot))
+                .build(),
+        );
+
+        assert_eq!(log.time(), timestamp);
+        assert_eq!(log.thread(), Some("captured-thread"));
+        assert!(log.params().contains_key("captured"));
+        assert!(!log.params().contains_key("current"));
+    }
+
+    #[test]
+    fn typed_keys() {
+        let mut snapshot = Snapshot::new();
+        snapshot.insert(UID, UserId("user".to_string()));
+        snapshot.insert(TRACE_ID, TraceId("0123456789abcdef".to_string()));
+        snapshot.safe_mut().insert(SID_KEY, 1);
+
+        let log = from_record(&Record::builder().mdc(Some(&snapshot)).build());
+
+        assert_eq!(log.uid(), Some(&UserId("user".to_string())));
+        assert_eq!(
+            log.trace_id(),
+            Some(&TraceId("0123456789abcdef".to_string()))
+        );
+        assert_eq!(log.sid(), None);
+        assert!(log.params().is_empty());
+    }
+
+    #[test]
+    fn owned_record() {
+        let error = Error::internal_safe("foo")
+            .with_safe_param("bar", 1)
+            .with_unsafe_param("baz", 2);
+        let record = Record::builder()
+            .message("message")
+            .safe_params(&[("fizz", &"buzz")])
+            .error(Some(&error))
+            .timestamp(Some(Utc::now()))
+            .build();
+
+        let log = from_record(&record);
+        assert_eq!(log.params()["errorCause"], Any::new(["foo"]).unwrap());
+        assert!(log.stacktrace().is_some());
+
+        let owned = record.to_owned();
+        assert_eq!(owned.as_record(from_record), log);
+    }
+
+    #[derive(Debug)]
+    struct OuterError(io::Error);
+
+    impl fmt::Display for OuterError {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt.write_str("outer")
+        }
+    }
+
+    impl error::Error for OuterError {
+        fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn std_error() {
+        let error: &dyn error::Error = &OuterError(io::Error::other("inner"));
+        let record = Record::builder().error(Some(&error)).build();
+
+        let log = from_record(&record);
+        assert_eq!(
+            log.unsafe_params()["errorCause"],
+            Any::new(["outer", "inner"]).unwrap()
+        );
+        assert!(!log.params().contains_key("errorCause"));
+    }
+
+    #[test]
+    fn tags() {
+        let mut snapshot = Snapshot::new();
+        snapshot.tags_mut().insert("mdc", "value");
+        snapshot.tags_mut().insert("overridden", "mdc");
+
+        let log = from_record(
+            &Record::builder()
+                .tags(&[("record", "value"), ("overridden", "record")])
+                .mdc(Some(&snapshot))
+                .build(),
+        );
+
+        assert_eq!(log.tags()["mdc"], "value");
+        assert_eq!(log.tags()["record"], "value");
+        assert_eq!(log.tags()["overridden"], "record");
+        assert!(log.params().is_empty());
+    }
+
+    #[test]
+    fn current_context() {
+        let log = thread::Builder::new()
+            .name("current-thread".to_string())
+            .spawn(|| {
+                mdc::insert_safe("current", "value");
+                from_record(&Record::builder().build())
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+
+        assert_eq!(log.thread(), Some("current-thread"));
+        assert!(log.params().contains_key("current"));
+    }
+}
@@ -0,0 +1,446 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A lightweight span API which emits Witchcraft `trace.1` logs.
+//!
+//! A [`Span`] measures the duration of a unit of work. Spans form trees identified by a trace ID, with each span
+//! identified by its own span ID and the ID of its parent. When a span is entered, it becomes the current span of the
+//! thread and its trace ID is stored in the MDC under [`TRACE_ID`], so service logs created within it are
+//! associated with the trace. When a span finishes, it is emitted as a [`TraceLogV1`] to the global [`SpanSink`].
+//!
+//! # Examples
+//!
+//! ```
+//! use witchcraft_log_util::span::{Instrument, Span};
+//!
+//! let span = Span::start("handle-request");
+//! let _guard = span.enter();
+//! // service logs here will include the span's trace ID
+//! let child = Span::start("query-database");
+//! // ...
+//! child.finish();
+//!
+//! # async fn fetch() {}
+//! let future = fetch().instrument(Span::start("fetch"));
+//! ```
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use conjure_object::{DateTime, SafeLong, Utc};
+use pin_project::pin_project;
+use witchcraft_logging_api::objects::{self, TraceId, TraceLogV1};
+
+use crate::mdc::TRACE_ID;
+
+thread_local! {
+    static CURRENT: RefCell<Option<SpanContext>> = const { RefCell::new(None) };
+}
+
+/// A destination for completed spans.
+pub trait SpanSink: Sync + Send {
+    /// Emits a completed span.
+    fn emit(&self, span: TraceLogV1);
+}
+
+struct NopSink;
+
+impl SpanSink for NopSink {
+    fn emit(&self, _: TraceLogV1) {}
+}
+
+static SINK: OnceLock<&'static dyn SpanSink> = OnceLock::new();
+
+/// Returns the global span sink.
+///
+/// If a sink has not been set, a no-op implementation is returned.
+pub fn sink() -> &'static dyn SpanSink {
+    SINK.get().copied().unwrap_or(&NopSink)
+}
+
+/// Sets the global span sink.
+///
+/// The global sink can only be set once. Further calls will return an error.
+pub fn set_sink(sink: &'static dyn SpanSink) -> Result<(), SetSinkError> {
+    SINK.set(sink).map_err(|_| SetSinkError(()))
+}
+
+/// Sets the global span sink to a `Box<dyn SpanSink>`.
+///
+/// The global sink can only be set once. Further calls will return an error.
+pub fn set_boxed_sink(sink: Box<dyn SpanSink>) -> Result<(), SetSinkError> {
+    let mut sink = Some(sink);
+    SINK.get_or_init(|| Box::leak(sink.take().unwrap()));
+    match sink {
+        Some(_) => Err(SetSinkError(())),
+        None => Ok(()),
+    }
+}
+
+/// The error returned when attempting to set the global span sink after it has already been set.
+#[derive(Debug)]
+pub struct SetSinkError(());
+
+impl fmt::Display for SetSinkError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("span sink already initialized")
+    }
+}
+
+impl Error for SetSinkError {}
+
+/// The identifiers of a span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpanContext {
+    trace_id: String,
+    span_id: String,
+}
+
+impl SpanContext {
+    /// Creates a new context from a trace ID and span ID.
+    ///
+    /// This can be used to continue a trace propagated from a remote service.
+    #[inline]
+    pub fn new(trace_id: impl Into<String>, span_id: impl Into<String>) -> Self {
+        SpanContext {
+            trace_id: trace_id.into(),
+            span_id: span_id.into(),
+        }
+    }
+
+    /// Returns the context's trace ID.
+    #[inline]
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// Returns the context's span ID.
+    #[inline]
+    pub fn span_id(&self) -> &str {
+        &self.span_id
+    }
+}
+
+/// Returns the context of the span currently entered on this thread.
+pub fn current() -> Option<SpanContext> {
+    CURRENT.with(|c| c.borrow().clone())
+}
+
+fn new_id() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+
+/// A span measuring a unit of work.
+///
+/// The span is emitted to the global [`SpanSink`] when it is finished or dropped.
+pub struct Span {
+    context: SpanContext,
+    parent_id: Option<String>,
+    name: String,
+    start_time: DateTime<Utc>,
+    start: Instant,
+    tags: BTreeMap<String, String>,
+}
+
+impl Span {
+    /// Starts a new span which is a child of the current span.
+    ///
+    /// If no span is currently entered, the span will be the root of a new trace. The trace ID stored in the MDC is
+    /// used if present, and a new one is generated otherwise.
+    pub fn start(name: impl Into<String>) -> Self {
+        match current() {
+            Some(parent) => Span::with_parent(&parent, name),
+            None => {
+                let trace_id = TRACE_ID.get().map_or_else(new_id, |t| t.0);
+                Span::new(trace_id, None, name.into())
+            }
+        }
+    }
+
+    /// Starts a new span which is the root of a new trace.
+    pub fn root(name: impl Into<String>) -> Self {
+        Span::new(new_id(), None, name.into())
+    }
+
+    /// Starts a new span which is a child of the span with the specified context.
+    pub fn with_parent(parent: &SpanContext, name: impl Into<String>) -> Self {
+        Span::new(
+            parent.trace_id.clone(),
+            Some(parent.span_id.clone()),
+            name.into(),
+        )
+    }
+
+    fn new(trace_id: String, parent_id: Option<String>, name: String) -> Self {
+        Span {
+            context: SpanContext {
+                trace_id,
+                span_id: new_id(),
+            },
+            parent_id,
+            name,
+            start_time: Utc::now(),
+            start: Instant::now(),
+            tags: BTreeMap::new(),
+        }
+    }
+
+    /// Starts a new span which is a child of this span.
+    pub fn child(&self, name: impl Into<String>) -> Self {
+        Span::with_parent(&self.context, name)
+    }
+
+    /// Returns the span's context.
+    #[inline]
+    pub fn context(&self) -> &SpanContext {
+        &self.context
+    }
+
+    /// Returns the span's name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the span ID of the span's parent, if it has one.
+    #[inline]
+    pub fn parent_id(&self) -> Option<&str> {
+        self.parent_id.as_deref()
+    }
+
+    /// Adds a tag to the span.
+    pub fn insert_tag(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.tags.insert(key.into(), value.into());
+    }
+
+    /// Makes this span the current span of the thread until the returned guard is dropped.
+    ///
+    /// The span's trace ID is also stored in the MDC for the guard's lifetime.
+    pub fn enter(&self) -> Entered<'_> {
+        let old_context = CURRENT.with(|c| c.borrow_mut().replace(self.context.clone()));
+        let old_trace_id = TRACE_ID.insert(TraceId(self.context.trace_id.clone()));
+
+        Entered {
+            old_context,
+            old_trace_id,
+            _p: PhantomData,
+        }
+    }
+
+    /// Finishes the span, emitting it to the global span sink.
+    ///
+    /// This is equivalent to dropping the span.
+    pub fn finish(self) {}
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let duration = i64::try_from(self.start.elapsed().as_micros()).unwrap_or(i64::MAX);
+
+        let span = objects::Span::builder()
+            .trace_id(&*self.context.trace_id)
+            .id(&*self.context.span_id)
+            .name(&*self.name)
+            .timestamp(safe_long(self.start_time.timestamp_micros()))
+            .duration(safe_long(duration))
+            .parent_id(self.parent_id.clone())
+            .tags(self.tags.clone())
+            .build();
+
+        sink().emit(
+            TraceLogV1::builder()
+                .type_("trace.1")
+                .time(self.start_time)
+                .span(span)
+                .build(),
+        );
+    }
+}
+
+fn safe_long(value: i64) -> SafeLong {
+    SafeLong::new(value).unwrap_or_else(|_| SafeLong::max_value())
+}
+
+/// A guard which resets the current span when it drops.
+pub struct Entered<'a> {
+    old_context: Option<SpanContext>,
+    old_trace_id: Option<TraceId>,
+    // the guard is tied to the current thread
+    _p: PhantomData<(&'a Span, *const ())>,
+}
+
+impl Drop for Entered<'_> {
+    fn drop(&mut self) {
+        CURRENT.with(|c| *c.borrow_mut() = self.old_context.take());
+        match self.old_trace_id.take() {
+            Some(trace_id) => {
+                TRACE_ID.insert(trace_id);
+            }
+            None => {
+                TRACE_ID.remove();
+            }
+        }
+    }
+}
+
+/// An extension trait to instrument futures with spans.
+pub trait Instrument: Sized {
+    /// Wraps the future so that the span is entered each time it is polled, and finished when it completes.
+    fn instrument(self, span: Span) -> Instrumented<Self> {
+        Instrumented {
+            future: self,
+            span: Some(span),
+        }
+    }
+}
+
+impl<F> Instrument for F where F: Future {}
+
+/// A future which enters a span each time it is polled.
+#[pin_project]
+pub struct Instrumented<F> {
+    #[pin]
+    future: F,
+    span: Option<Span>,
+}
+
+impl<F> Future for Instrumented<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let guard = this.span.as_ref().map(Span::enter);
+        let output = this.future.poll(cx);
+        drop(guard);
+
+        if output.is_ready() {
+            this.span.take();
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use witchcraft_log::mdc;
+
+    use super::*;
+    use crate::mdc::TRACE_ID_KEY;
+
+    static SPANS: Mutex<Vec<TraceLogV1>> = Mutex::new(vec![]);
+
+    struct TestSink;
+
+    impl SpanSink for TestSink {
+        fn emit(&self, span: TraceLogV1) {
+            SPANS.lock().unwrap().push(span);
+        }
+    }
+
+    fn init() {
+        let _ = set_sink(&TestSink);
+    }
+
+    fn spans(trace_id: &str) -> Vec<objects::Span> {
+        SPANS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|s| s.span().trace_id() == trace_id)
+            .map(|s| s.span().clone())
+            .collect()
+    }
+
+    #[test]
+    fn nested() {
+        init();
+
+        let mut root = Span::root("root");
+        root.insert_tag("foo", "bar");
+        let trace_id = root.context().trace_id().to_string();
+        {
+            let _guard = root.enter();
+            assert_eq!(current().as_ref(), Some(root.context()));
+            assert_eq!(
+                mdc::snapshot().safe().get(TRACE_ID_KEY),
+                Some(&conjure_object::Any::new(&trace_id).unwrap()),
+            );
+
+            let child = Span::start("child");
+            assert_eq!(child.context().trace_id(), trace_id);
+            assert_eq!(child.parent_id(), Some(root.context().span_id()));
+            child.finish();
+        }
+        assert_eq!(current(), None);
+        assert!(mdc::snapshot().safe().get(TRACE_ID_KEY).is_none());
+        let root_id = root.context().span_id().to_string();
+        root.finish();
+
+        let spans = spans(&trace_id);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].name(), "child");
+        assert_eq!(spans[0].parent_id(), Some(&*root_id));
+        assert_eq!(spans[1].name(), "root");
+        assert_eq!(spans[1].id(), root_id);
+        assert_eq!(spans[1].parent_id(), None);
+        assert_eq!(spans[1].tags()["foo"], "bar");
+    }
+
+    #[test]
+    fn mdc_trace_id() {
+        let _guard = mdc::scope();
+        mdc::insert_safe(TRACE_ID_KEY, "0123456789abcdef");
+
+        let span = Span::start("span");
+        assert_eq!(span.context().trace_id(), "0123456789abcdef");
+        assert_eq!(span.parent_id(), None);
+    }
+
+    #[test]
+    fn instrument() {
+        init();
+
+        let span = Span::root("future");
+        let trace_id = span.context().trace_id().to_string();
+        let span_id = span.context().span_id().to_string();
+
+        futures_executor::block_on(
+            async {
+                assert_eq!(current().unwrap().span_id(), span_id);
+                Span::start("inner").finish();
+            }
+            .instrument(span),
+        );
+        assert_eq!(current(), None);
+
+        let spans = spans(&trace_id);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].name(), "inner");
+        assert_eq!(spans[0].parent_id(), Some(&*span_id));
+        assert_eq!(spans[1].name(), "future");
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Fixtures shared by the crate's tests.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use witchcraft_log::{Level, LevelFilter, Log, Metadata, OwnedRecord, Record};
+
+/// A logger which records everything at or above its level.
+#[derive(Clone)]
+pub struct TestLogger {
+    level: LevelFilter,
+    records: Arc<Mutex<Vec<OwnedRecord>>>,
+    flushes: Arc<AtomicUsize>,
+}
+
+impl TestLogger {
+    pub fn new(level: LevelFilter) -> Self {
+        TestLogger {
+            level,
+            records: Arc::new(Mutex::new(vec![])),
+            flushes: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn messages(&self) -> Vec<String> {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.message().to_string())
+            .collect()
+    }
+
+    pub fn flushes(&self) -> usize {
+        self.flushes.load(Ordering::SeqCst)
+    }
+}
+
+impl Log for TestLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            self.records.lock().unwrap().push(record.to_owned());
+        }
+    }
+
+    fn flush(&self) {
+        self.flushes.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.level
+    }
+}
+
+pub fn log(logger: &dyn Log, level: Level, target: &str, message: &'static str) {
+    logger.log(
+        &Record::builder()
+            .level(level)
+            .target(target)
+            .message(message)
+            .build(),
+    );
+}
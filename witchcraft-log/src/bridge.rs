@@ -16,6 +16,17 @@
 //! Even if your application uses this crate for logging, many of its dependencies probably use the `log` crate.
 //! This module can be used to configure the `log` crate to forward its messages to `witchcraft-log`.
 //!
+//! Static messages are safe to use as the Witchcraft message, but formatted messages are conservatively included as
+//! the unsafe `message` parameter. Key-values attached to records via the `log` crate's `kv` API are included as
+//! unsafe parameters.
+//!
+//! [`BridgedLogger`] applies that policy to every record. A [`ConfiguredBridgedLogger`], created with
+//! [`BridgedLogger::builder`], can additionally register keys as safe with [`Builder::safe_key`] or identify them by a
+//! prefix with [`Builder::safe_key_prefix`], and can override the policy for specific targets: records from trusted
+//! targets have their formatted messages and key-values treated as safe, levels can be remapped, and targets can be
+//! renamed. Targets are matched by module path, so a rule for `hyper` also applies to `hyper::proto`. Rules always
+//! match against the original `log` target, even if it is renamed.
+//!
 //! # Examples
 //!
 //! ```
@@ -36,13 +47,144 @@
 //! // That won't happen automatically.
 //! witchcraft_log::bridge::set_max_level(LevelFilter::Warn);
 //! ```
+//!
+//! With a configured logger:
+//!
+//! ```
+//! use witchcraft_log::bridge::{self, BridgedLogger};
+//! use witchcraft_log::{Level, LevelFilter};
+//! # struct MyWitchcraftLogger;
+//! # impl witchcraft_log::Log for MyWitchcraftLogger {
+//! #    fn enabled(&self, _: &witchcraft_log::Metadata<'_>) -> bool { false }
+//! #    fn log(&self, _: &witchcraft_log::Record<'_>) {}
+//! #    fn flush(&self) {}
+//! # }
+//!
+//! witchcraft_log::set_logger(&MyWitchcraftLogger);
+//! witchcraft_log::set_max_level(LevelFilter::Warn);
+//!
+//! let logger = BridgedLogger::builder()
+//!     .trusted_target("my_service")
+//!     .remap_level("hyper", log::Level::Info, Level::Debug)
+//!     .rename_target("hyper", "http::hyper")
+//!     .build();
+//! // The log crate's max level will be kept in sync with witchcraft_log's.
+//! bridge::init(logger).unwrap();
+//! ```
 
-use crate::{Level, LevelFilter, Metadata, Record};
+use crate::{mdc, Level, LevelFilter, Metadata, Record};
+use conjure_object::Utc;
+use erased_serde::Serialize;
+use log::kv::{self, Key, Value, VisitSource};
 use log::Log;
+use std::borrow::Cow;
+use std::sync::OnceLock;
+use std::thread;
+
+static INSTALLED: OnceLock<&'static ConfiguredBridgedLogger> = OnceLock::new();
+
+static DEFAULT: ConfiguredBridgedLogger = ConfiguredBridgedLogger {
+    safe_keys: vec![],
+    safe_key_prefix: None,
+    trusted_targets: vec![],
+    level_remaps: vec![],
+    target_renames: vec![],
+};
 
 /// A `log::Log` implementation that forwards records to the `witchcraft-log` logger.
 pub struct BridgedLogger;
 
+impl BridgedLogger {
+    /// Returns a builder used to configure a [`ConfiguredBridgedLogger`].
+    pub fn builder() -> Builder {
+        Builder {
+            safe_keys: vec![],
+            safe_key_prefix: None,
+            trusted_targets: vec![],
+            level_remaps: vec![],
+            target_renames: vec![],
+        }
+    }
+}
+
+impl Log for BridgedLogger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        DEFAULT.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        DEFAULT.log(record)
+    }
+
+    fn flush(&self) {
+        DEFAULT.flush()
+    }
+}
+
+/// A `log::Log` implementation that forwards records to the `witchcraft-log` logger with a custom configuration.
+///
+/// It is created by a [`Builder`].
+pub struct ConfiguredBridgedLogger {
+    safe_keys: Vec<String>,
+    safe_key_prefix: Option<String>,
+    trusted_targets: Vec<String>,
+    level_remaps: Vec<(String, log::Level, Level)>,
+    target_renames: Vec<(String, String)>,
+}
+
+impl ConfiguredBridgedLogger {
+    fn safe_key<'a>(&self, key: &'a str) -> Option<&'a str> {
+        if self.safe_keys.iter().any(|k| k == key) {
+            return Some(key);
+        }
+
+        self.safe_key_prefix
+            .as_ref()
+            .and_then(|prefix| key.strip_prefix(&**prefix))
+            .filter(|key| !key.is_empty())
+    }
+
+    fn trusted(&self, target: &str) -> bool {
+        self.trusted_targets.iter().any(|t| matches(target, t))
+    }
+
+    fn level(&self, target: &str, level: log::Level) -> Level {
+        self.level_remaps
+            .iter()
+            .filter(|(t, from, _)| *from == level && matches(target, t))
+            .max_by_key(|(t, _, _)| t.len())
+            .map_or_else(|| cvt_level(level), |(_, _, to)| *to)
+    }
+
+    fn target<'a>(&self, target: &'a str) -> Cow<'a, str> {
+        match self
+            .target_renames
+            .iter()
+            .filter(|(from, _)| matches(target, from))
+            .max_by_key(|(from, _)| from.len())
+        {
+            Some((from, to)) => Cow::Owned(format!("{}{}", to, &target[from.len()..])),
+            None => Cow::Borrowed(target),
+        }
+    }
+
+    fn log_max_level(&self, level: LevelFilter) -> log::LevelFilter {
+        // remapped levels may be more verbose on the log side than the witchcraft max level
+        self.level_remaps
+            .iter()
+            .filter(|(_, _, to)| *to <= level)
+            .map(|(_, from, _)| from.to_level_filter())
+            .fold(cvt_level_filter(level), |a, b| a.max(b))
+    }
+}
+
+// targets match by module path, so `foo` matches `foo` and `foo::bar` but not `foobar`
+fn matches(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
 fn cvt_level(level: log::Level) -> Level {
     match level {
         log::Level::Error => Level::Error,
@@ -53,36 +195,76 @@ fn cvt_level(level: log::Level) -> Level {
     }
 }
 
-impl Log for BridgedLogger {
+fn cvt_level_filter(level: LevelFilter) -> log::LevelFilter {
+    match level {
+        LevelFilter::Trace => log::LevelFilter::Trace,
+        LevelFilter::Debug => log::LevelFilter::Debug,
+        LevelFilter::Info => log::LevelFilter::Info,
+        LevelFilter::Warn => log::LevelFilter::Warn,
+        LevelFilter::Error => log::LevelFilter::Error,
+        LevelFilter::Fatal | LevelFilter::Off => log::LevelFilter::Off,
+    }
+}
+
+impl Log for ConfiguredBridgedLogger {
     fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
         crate::logger().enabled(
             &Metadata::builder()
-                .level(cvt_level(metadata.level()))
-                .target(metadata.target())
+                .level(self.level(metadata.target(), metadata.level()))
+                .target(&self.target(metadata.target()))
                 .build(),
         )
     }
 
     fn log(&self, record: &log::Record<'_>) {
+        let thread = thread::current();
+        let mdc = mdc::snapshot();
+        let target = self.target(record.target());
+        let trusted = self.trusted(record.target());
+
         let mut builder = Record::builder();
         builder
-            .level(cvt_level(record.level()))
-            .target(record.target())
+            .level(self.level(record.target(), record.level()))
+            .target(&target)
             .file(record.file())
-            .line(record.line());
+            .line(record.line())
+            .timestamp(Some(Utc::now()))
+            .thread_name(thread.name())
+            .thread_id(Some(thread.id()))
+            .mdc(Some(&mdc));
+
+        let mut key_values = KeyValues {
+            logger: self,
+            trusted,
+            safe: vec![],
+            unsafe_: vec![],
+        };
+        let _ = record.key_values().visit(&mut key_values);
+
+        let mut safe_params = Vec::<(&str, &dyn Serialize)>::new();
+        let mut unsafe_params = Vec::<(&str, &dyn Serialize)>::new();
 
         // If the log message is static, it is safe to log as the WC message. Otherwise, we have to conservatively
-        // assume it contains unsafe data.
+        // assume it contains unsafe data unless the target is trusted.
         let args = record.args();
-        let unsafe_params = [("message", args as _)];
         match args.as_str() {
             Some(message) => {
                 builder.message(message);
             }
-            None => {
-                builder.unsafe_params(&unsafe_params);
-            }
+            None if trusted => safe_params.push(("message", args)),
+            None => unsafe_params.push(("message", args)),
         }
+
+        // the safe key prefix is removed from the names of safe parameters
+        safe_params.extend(key_values.safe.iter().map(|(k, v)| {
+            let key = k.as_str();
+            (self.safe_key(key).unwrap_or(key), v as _)
+        }));
+        unsafe_params.extend(key_values.unsafe_.iter().map(|(k, v)| (k.as_str(), v as _)));
+        builder
+            .safe_params(&safe_params)
+            .unsafe_params(&unsafe_params);
+
         crate::logger().log(&builder.build())
     }
 
@@ -91,17 +273,168 @@ impl Log for BridgedLogger {
     }
 }
 
+/// A builder for [`ConfiguredBridgedLogger`].
+pub struct Builder {
+    safe_keys: Vec<String>,
+    safe_key_prefix: Option<String>,
+    trusted_targets: Vec<String>,
+    level_remaps: Vec<(String, log::Level, Level)>,
+    target_renames: Vec<(String, String)>,
+}
+
+impl Builder {
+    /// Registers a key whose values will be treated as safe parameters.
+    pub fn safe_key(mut self, key: &str) -> Self {
+        self.safe_keys.push(key.to_string());
+        self
+    }
+
+    /// Sets a prefix identifying keys whose values will be treated as safe parameters.
+    ///
+    /// The prefix is removed from the parameter's name.
+    pub fn safe_key_prefix(mut self, prefix: &str) -> Self {
+        self.safe_key_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Registers a target whose formatted messages and key-values will all be treated as safe.
+    pub fn trusted_target(mut self, target: &str) -> Self {
+        self.trusted_targets.push(target.to_string());
+        self
+    }
+
+    /// Maps records from a target at the `from` level to the `to` level.
+    ///
+    /// If multiple rules match a record, the one with the most specific target is used.
+    pub fn remap_level(mut self, target: &str, from: log::Level, to: Level) -> Self {
+        self.level_remaps.push((target.to_string(), from, to));
+        self
+    }
+
+    /// Renames a target, along with all of its submodules.
+    ///
+    /// If multiple rules match a record, the one with the most specific target is used.
+    pub fn rename_target(mut self, from: &str, to: &str) -> Self {
+        self.target_renames.push((from.to_string(), to.to_string()));
+        self
+    }
+
+    /// Creates the logger.
+    pub fn build(self) -> ConfiguredBridgedLogger {
+        ConfiguredBridgedLogger {
+            safe_keys: self.safe_keys,
+            safe_key_prefix: self.safe_key_prefix,
+            trusted_targets: self.trusted_targets,
+            level_remaps: self.level_remaps,
+            target_renames: self.target_renames,
+        }
+    }
+}
+
+struct KeyValues<'a, 'kvs> {
+    logger: &'a ConfiguredBridgedLogger,
+    trusted: bool,
+    safe: Vec<(Key<'kvs>, Value<'kvs>)>,
+    unsafe_: Vec<(Key<'kvs>, Value<'kvs>)>,
+}
+
+impl<'kvs> VisitSource<'kvs> for KeyValues<'_, 'kvs> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        if self.trusted || self.logger.safe_key(key.as_str()).is_some() {
+            self.safe.push((key, value));
+        } else {
+            self.unsafe_.push((key, value));
+        }
+        Ok(())
+    }
+}
+
+/// Installs a bridged logger as the `log` crate's logger.
+///
+/// The `log` crate's max level is set from `witchcraft_log`'s, and is automatically updated whenever
+/// `witchcraft_log::set_max_level` is called, accounting for any remapped levels.
+///
+/// Returns an error if the `log` crate's logger has already been set.
+pub fn init(logger: ConfiguredBridgedLogger) -> Result<(), log::SetLoggerError> {
+    let logger = Box::leak(Box::new(logger));
+    log::set_logger(logger)?;
+    let _ = INSTALLED.set(logger);
+    set_max_level(crate::max_level());
+
+    Ok(())
+}
+
 /// Sets the `log` crate's max log level.
 ///
-/// This simply translates from a `witchcraft_log::LevelFilter` to a `log::LevelFilter` and calls `log::set_max_level`.
+/// This translates from a `witchcraft_log::LevelFilter` to a `log::LevelFilter` and calls `log::set_max_level`. If
+/// the logger was installed with [`init`], levels it remaps are taken into account. This does not need to be called
+/// manually in that case, since `witchcraft_log::set_max_level` will call it automatically.
 pub fn set_max_level(level: LevelFilter) {
-    let level = match level {
-        LevelFilter::Trace => log::LevelFilter::Trace,
-        LevelFilter::Debug => log::LevelFilter::Debug,
-        LevelFilter::Info => log::LevelFilter::Info,
-        LevelFilter::Warn => log::LevelFilter::Warn,
-        LevelFilter::Error => log::LevelFilter::Error,
-        LevelFilter::Fatal | LevelFilter::Off => log::LevelFilter::Off,
+    let level = match INSTALLED.get() {
+        Some(logger) => logger.log_max_level(level),
+        None => cvt_level_filter(level),
     };
     log::set_max_level(level);
 }
+
+pub(crate) fn sync_max_level(level: LevelFilter) {
+    if INSTALLED.get().is_some() {
+        set_max_level(level);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bridge::BridgedLogger;
+    use crate::{Level, LevelFilter};
+
+    #[test]
+    fn rules() {
+        let logger = BridgedLogger::builder()
+            .remap_level("hyper", log::Level::Info, Level::Debug)
+            .remap_level("hyper::proto", log::Level::Info, Level::Warn)
+            .remap_level("noisy", log::Level::Trace, Level::Info)
+            .rename_target("hyper", "http::hyper")
+            .rename_target("hyper::client", "http::client")
+            .trusted_target("my_crate")
+            .build();
+
+        assert_eq!(logger.level("hyper", log::Level::Info), Level::Debug);
+        assert_eq!(
+            logger.level("hyper::server", log::Level::Info),
+            Level::Debug
+        );
+        assert_eq!(
+            logger.level("hyper::proto::h1", log::Level::Info),
+            Level::Warn
+        );
+        assert_eq!(logger.level("hyper", log::Level::Warn), Level::Warn);
+        assert_eq!(logger.level("hyperx", log::Level::Info), Level::Info);
+
+        assert_eq!(logger.target("hyper"), "http::hyper");
+        assert_eq!(logger.target("hyper::server"), "http::hyper::server");
+        assert_eq!(logger.target("hyper::client::pool"), "http::client::pool");
+        assert_eq!(logger.target("hyperx"), "hyperx");
+
+        assert!(logger.trusted("my_crate"));
+        assert!(logger.trusted("my_crate::foo"));
+        assert!(!logger.trusted("my_crate_2"));
+
+        assert_eq!(
+            logger.log_max_level(LevelFilter::Warn),
+            log::LevelFilter::Info
+        );
+        assert_eq!(
+            logger.log_max_level(LevelFilter::Info),
+            log::LevelFilter::Trace
+        );
+        assert_eq!(
+            logger.log_max_level(LevelFilter::Error),
+            log::LevelFilter::Error
+        );
+        assert_eq!(
+            logger.log_max_level(LevelFilter::Off),
+            log::LevelFilter::Off
+        );
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use conjure_error::{Error, ErrorKind, SerializableError};
+use conjure_object::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error;
+use std::fmt;
+
+/// An error which can be associated with a log record.
+///
+/// This is implemented for `conjure_error::Error`, [`OwnedError`](crate::OwnedError), and `dyn std::error::Error`.
+/// The logging macros additionally accept any type implementing `std::error::Error` directly, and any type which derefs
+/// to a `LogError`, such as `Box<dyn std::error::Error + Send + Sync>`.
+///
+/// Standard errors never report backtraces through this trait, even if they captured one internally, since there is
+/// no way to retrieve a backtrace from a `dyn std::error::Error` on stable Rust. Wrap the error in [`Backtraced`] at
+/// the point it is logged to include a backtrace in the record.
+pub trait LogError {
+    /// Returns the messages of the error's cause chain, starting with the error's cause itself.
+    fn causes(&self) -> Vec<String>;
+
+    /// Returns whether or not the error's cause is considered safe.
+    ///
+    /// Defaults to `false`.
+    fn cause_safe(&self) -> bool {
+        false
+    }
+
+    /// Returns the service error information, if the error is a service error.
+    ///
+    /// Defaults to `None`.
+    fn service_error(&self) -> Option<&SerializableError> {
+        None
+    }
+
+    /// Returns the error's safe parameters.
+    ///
+    /// Defaults to no parameters.
+    fn safe_params(&self) -> Vec<(&str, &Any)> {
+        vec![]
+    }
+
+    /// Returns the error's unsafe parameters.
+    ///
+    /// Defaults to no parameters.
+    fn unsafe_params(&self) -> Vec<(&str, &Any)> {
+        vec![]
+    }
+
+    /// Returns the error's formatted backtraces, ordered from oldest to newest.
+    ///
+    /// Defaults to no backtraces.
+    fn backtraces(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+impl<T> LogError for &T
+where
+    T: ?Sized + LogError,
+{
+    fn causes(&self) -> Vec<String> {
+        (**self).causes()
+    }
+
+    fn cause_safe(&self) -> bool {
+        (**self).cause_safe()
+    }
+
+    fn service_error(&self) -> Option<&SerializableError> {
+        (**self).service_error()
+    }
+
+    fn safe_params(&self) -> Vec<(&str, &Any)> {
+        (**self).safe_params()
+    }
+
+    fn unsafe_params(&self) -> Vec<(&str, &Any)> {
+        (**self).unsafe_params()
+    }
+
+    fn backtraces(&self) -> Vec<String> {
+        (**self).backtraces()
+    }
+}
+
+impl LogError for Error {
+    fn causes(&self) -> Vec<String> {
+        causes(self.cause())
+    }
+
+    fn cause_safe(&self) -> bool {
+        Error::cause_safe(self)
+    }
+
+    fn service_error(&self) -> Option<&SerializableError> {
+        match self.kind() {
+            ErrorKind::Service(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    fn safe_params(&self) -> Vec<(&str, &Any)> {
+        Error::safe_params(self).iter().collect()
+    }
+
+    fn unsafe_params(&self) -> Vec<(&str, &Any)> {
+        Error::unsafe_params(self).iter().collect()
+    }
+
+    fn backtraces(&self) -> Vec<String> {
+        Error::backtraces(self)
+            .iter()
+            .map(|b| format!("{b:?}"))
+            .collect()
+    }
+}
+
+impl LogError for dyn error::Error + 'static {
+    fn causes(&self) -> Vec<String> {
+        causes(self)
+    }
+}
+
+impl LogError for dyn error::Error + Sync + Send + 'static {
+    fn causes(&self) -> Vec<String> {
+        causes(self)
+    }
+}
+
+fn causes(error: &(dyn error::Error + 'static)) -> Vec<String> {
+    let mut causes = vec![];
+    let mut cause = Some(error);
+    while let Some(e) = cause {
+        causes.push(e.to_string());
+        cause = e.source();
+    }
+    causes
+}
+
+/// A wrapper which attaches a [`Backtrace`] to an error.
+///
+/// Standard library errors cannot expose their backtraces to loggers on stable Rust, so this can be used to include
+/// one in the record. Note that the backtrace is captured where the wrapper is created rather than where the error
+/// originated, and any backtrace captured inside the wrapped error itself is not included. The backtrace is only
+/// included if it was actually captured, as controlled by the `RUST_BACKTRACE` and `RUST_LIB_BACKTRACE` environment
+/// variables.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::Backtraced;
+///
+/// # fn read_config() -> std::io::Result<()> { Ok(()) }
+/// if let Err(e) = read_config() {
+///     witchcraft_log::error!("error reading config", error: Backtraced::new(e));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Backtraced<E> {
+    error: E,
+    backtrace: Backtrace,
+}
+
+impl<E> Backtraced<E> {
+    /// Wraps an error, capturing a backtrace.
+    pub fn new(error: E) -> Self {
+        Backtraced {
+            error,
+            backtrace: Backtrace::capture(),
+        }
+    }
+
+    /// Returns a shared reference to the wrapped error.
+    #[inline]
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Returns the captured backtrace.
+    #[inline]
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+
+    /// Returns the wrapped error.
+    #[inline]
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E> fmt::Display for Backtraced<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, fmt)
+    }
+}
+
+impl<E> error::Error for Backtraced<E>
+where
+    E: error::Error,
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+impl<E> LogError for Backtraced<E>
+where
+    E: error::Error + 'static,
+{
+    fn causes(&self) -> Vec<String> {
+        causes(&self.error)
+    }
+
+    fn backtraces(&self) -> Vec<String> {
+        match self.backtrace.status() {
+            BacktraceStatus::Captured => vec![self.backtrace.to_string()],
+            _ => vec![],
+        }
+    }
+}
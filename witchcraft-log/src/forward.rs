@@ -0,0 +1,309 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for forwarding records from `witchcraft-log` to the `log` crate.
+//!
+//! This module is only available with the `forward` Cargo feature enabled. Libraries using `witchcraft-log` may be used
+//! in applications which only configure the `log` crate. This module provides a [`ForwardingLogger`] which can be
+//! installed as the `witchcraft-log` logger in that case.
+//!
+//! Since the `log` crate has no notion of parameter safety, unsafe parameters are dropped unless explicitly allowed.
+//! Included parameters are rendered into the formatted message as `key=value` pairs, with values serialized as JSON,
+//! and are also attached to the record as `log::kv` key-values.
+//!
+//! This should not be combined with the [`bridge`](crate::bridge) module, since records would be forwarded back and
+//! forth between the two crates forever.
+//!
+//! # Examples
+//!
+//! ```
+//! use witchcraft_log::forward::ForwardingLogger;
+//! use witchcraft_log::LevelFilter;
+//!
+//! witchcraft_log::set_boxed_logger(Box::new(ForwardingLogger::new()));
+//! witchcraft_log::set_max_level(LevelFilter::Info);
+//!
+//! // after the `log` crate's filters change
+//! log::set_max_level(log::LevelFilter::Warn);
+//! witchcraft_log::rebuild_interest_cache();
+//! ```
+use crate::{Level, LevelFilter, Log, Metadata, Record};
+use erased_serde::Serialize;
+use log::kv::{self, Key, Source, Value, VisitSource};
+use std::fmt::Write;
+
+fn cvt_level(level: Level) -> log::Level {
+    match level {
+        Level::Fatal | Level::Error => log::Level::Error,
+        Level::Warn => log::Level::Warn,
+        Level::Info => log::Level::Info,
+        Level::Debug => log::Level::Debug,
+        Level::Trace => log::Level::Trace,
+    }
+}
+
+/// A `witchcraft_log::Log` implementation that forwards records to the `log` crate's logger.
+///
+/// The "fatal" level is mapped to the `log` crate's "error" level.
+///
+/// Whether a record is enabled is determined by the `log` crate's logger, but the logging macros cache that result per
+/// call site. [`rebuild_interest_cache`](crate::rebuild_interest_cache) must be called whenever the `log` crate's
+/// logger or its filters are reconfigured after this logger has been installed, or the change may be ignored.
+pub struct ForwardingLogger {
+    unsafe_params: bool,
+}
+
+impl Default for ForwardingLogger {
+    fn default() -> Self {
+        ForwardingLogger::new()
+    }
+}
+
+impl ForwardingLogger {
+    /// Creates a new logger with the default configuration.
+    ///
+    /// Unsafe parameters are not forwarded.
+    pub fn new() -> Self {
+        ForwardingLogger::builder().build()
+    }
+
+    /// Returns a builder used to configure a logger.
+    pub fn builder() -> Builder {
+        Builder {
+            unsafe_params: false,
+        }
+    }
+
+    fn forward(&self, record: &Record<'_>, logger: &dyn log::Log) {
+        let mut params = record.safe_params().to_vec();
+        if self.unsafe_params {
+            params.extend_from_slice(record.unsafe_params());
+        }
+
+        let mut message = record.message().to_string();
+        for (key, value) in &params {
+            if let Ok(value) = serde_json::to_string(value) {
+                let _ = write!(message, " {key}={value}");
+            }
+        }
+
+        let causes = record
+            .error()
+            .filter(|e| self.unsafe_params || e.cause_safe())
+            .map(|e| e.causes().join(": "));
+        if let Some(causes) = &causes {
+            let _ = write!(message, " error={causes}");
+        }
+
+        let key_values = KeyValues {
+            params: &params,
+            error: causes.as_deref(),
+        };
+
+        logger.log(
+            &log::Record::builder()
+                .args(format_args!("{message}"))
+                .level(cvt_level(record.level()))
+                .target(record.target())
+                .file(record.file())
+                .line(record.line())
+                .key_values(&key_values)
+                .build(),
+        );
+    }
+}
+
+impl Log for ForwardingLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        log::logger().enabled(
+            &log::Metadata::builder()
+                .level(cvt_level(metadata.level()))
+                .target(metadata.target())
+                .build(),
+        )
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        self.forward(record, log::logger());
+    }
+
+    fn flush(&self) {
+        log::logger().flush();
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        match log::max_level() {
+            log::LevelFilter::Off => LevelFilter::Off,
+            log::LevelFilter::Error => LevelFilter::Error,
+            log::LevelFilter::Warn => LevelFilter::Warn,
+            log::LevelFilter::Info => LevelFilter::Info,
+            log::LevelFilter::Debug => LevelFilter::Debug,
+            log::LevelFilter::Trace => LevelFilter::Trace,
+        }
+    }
+}
+
+/// A builder for [`ForwardingLogger`].
+pub struct Builder {
+    unsafe_params: bool,
+}
+
+impl Builder {
+    /// Determines if unsafe parameters and unsafe error causes will be forwarded.
+    ///
+    /// Defaults to `false`.
+    pub fn unsafe_params(mut self, unsafe_params: bool) -> Self {
+        self.unsafe_params = unsafe_params;
+        self
+    }
+
+    /// Creates the logger.
+    pub fn build(self) -> ForwardingLogger {
+        ForwardingLogger {
+            unsafe_params: self.unsafe_params,
+        }
+    }
+}
+
+struct KeyValues<'a> {
+    params: &'a [(&'a str, &'a dyn Serialize)],
+    error: Option<&'a str>,
+}
+
+impl Source for KeyValues<'_> {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), kv::Error> {
+        for (key, value) in self.params {
+            visitor.visit_pair(Key::from_str(key), Value::from_serde(value))?;
+        }
+        if let Some(error) = self.error {
+            visitor.visit_pair(Key::from_str("error"), Value::from(error))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::forward::ForwardingLogger;
+    use crate::Record;
+    use conjure_error::Error;
+    use log::kv::{self, Key, Value, VisitSource};
+    use std::sync::Mutex;
+    use std::{fmt, io};
+
+    #[derive(Debug, PartialEq)]
+    struct LogRecord {
+        level: log::Level,
+        target: String,
+        message: String,
+        key_values: Vec<(String, String)>,
+    }
+
+    struct TestLogger(Mutex<Vec<LogRecord>>);
+
+    impl log::Log for TestLogger {
+        fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record<'_>) {
+            struct Collect(Vec<(String, String)>);
+
+            impl<'kvs> VisitSource<'kvs> for Collect {
+                fn visit_pair(
+                    &mut self,
+                    key: Key<'kvs>,
+                    value: Value<'kvs>,
+                ) -> Result<(), kv::Error> {
+                    self.0.push((key.to_string(), value.to_string()));
+                    Ok(())
+                }
+            }
+
+            let mut key_values = Collect(vec![]);
+            record.key_values().visit(&mut key_values).unwrap();
+            self.0.lock().unwrap().push(LogRecord {
+                level: record.level(),
+                target: record.target().to_string(),
+                message: fmt::format(*record.args()),
+                key_values: key_values.0,
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    fn forward(logger: ForwardingLogger, record: &Record<'_>) -> LogRecord {
+        let test = TestLogger(Mutex::new(vec![]));
+        logger.forward(record, &test);
+        test.0.into_inner().unwrap().pop().unwrap()
+    }
+
+    #[test]
+    fn forward_params() {
+        let error = Error::internal_safe(io::Error::other("bad yak"));
+        let record = Record::builder()
+            .level(crate::Level::Fatal)
+            .target("foo::bar")
+            .message("shaving yak")
+            .safe_params(&[("attempt", &3)])
+            .unsafe_params(&[("yak", &"bob")])
+            .error(Some(&error))
+            .build();
+
+        assert_eq!(
+            forward(ForwardingLogger::new(), &record),
+            LogRecord {
+                level: log::Level::Error,
+                target: "foo::bar".to_string(),
+                message: "shaving yak attempt=3 error=bad yak".to_string(),
+                key_values: vec![
+                    ("attempt".to_string(), "3".to_string()),
+                    ("error".to_string(), "bad yak".to_string()),
+                ],
+            },
+        );
+
+        assert_eq!(
+            forward(
+                ForwardingLogger::builder().unsafe_params(true).build(),
+                &record
+            ),
+            LogRecord {
+                level: log::Level::Error,
+                target: "foo::bar".to_string(),
+                message: r#"shaving yak attempt=3 yak="bob" error=bad yak"#.to_string(),
+                key_values: vec![
+                    ("attempt".to_string(), "3".to_string()),
+                    ("yak".to_string(), r#""bob""#.to_string()),
+                    ("error".to_string(), "bad yak".to_string()),
+                ],
+            },
+        );
+    }
+
+    #[test]
+    fn unsafe_error() {
+        let error = Error::internal(io::Error::other("secret yak"));
+        let record = Record::builder()
+            .level(crate::Level::Info)
+            .message("shaving yak")
+            .error(Some(&error))
+            .build();
+
+        let forwarded = forward(ForwardingLogger::new(), &record);
+        assert_eq!(forwarded.level, log::Level::Info);
+        assert_eq!(forwarded.message, "shaving yak");
+        assert_eq!(forwarded.key_values, vec![]);
+    }
+}
@@ -30,6 +30,21 @@
 //! Parameters can be arbitrary `serde`-serializable values. Note, however, that loggers may commonly serialize
 //! parameters to JSON, so values that cannot be serialized into JSON are not recommended.
 //!
+//! Types which are logged repeatedly can implement [`LogParams`] to include their fields as parameters. With the
+//! `derive` Cargo feature enabled, it can be derived with each field's safety declared by an attribute.
+//!
+//! By default, any value can be logged as a safe parameter. With the `strict_safety` Cargo feature enabled, safe
+//! parameter values must instead implement the [`SafeArg`] marker trait, which is implemented for numbers, booleans,
+//! durations, and types which opt in. Values can be explicitly marked with the [`Safe`] and [`Unsafe`] wrappers.
+//!
+//! Note that unlike other Cargo features, `strict_safety` is not additive: since features are unified across a build,
+//! enabling it in one crate applies it to every crate logging through `witchcraft-log`, and any of them which log
+//! values not known to be safe will then fail to compile. It is intended to be enabled by applications rather than
+//! libraries.
+//!
+//! Records can additionally carry string-valued tags, which are used to categorize records rather than describe them.
+//! Tags can be set per record, or in the thread's MDC via [`mdc::insert_tag`].
+//!
 //! All dynamic information in the log record should be represented via parameters. In fact, Witchcraft-log requires the
 //! log message to be a static string - no interpolation of any kind can be performed. This means that the message
 //! itself can always be considered safe.
@@ -37,7 +52,7 @@
 //! ## Examples
 //!
 //! ```
-//! # let (user_id, memory_overhead) = ("", "");
+//! # let (user_id, memory_overhead) = ("", 0);
 //! // with the standard log crate
 //! log::info!("ran a request for {} using {} bytes of memory", user_id, memory_overhead);
 //!
@@ -47,8 +62,12 @@
 //!
 //! # Errors
 //!
-//! Additionally, a `conjure_error::Error` can be associated with a log message. Since many logs occur due to an error,
-//! this allows more information about the error (e.g. its stacktrace) to be automatically included in the record.
+//! Additionally, an error can be associated with a log message. Since many logs occur due to an error, this allows
+//! more information about the error (e.g. its stacktrace) to be automatically included in the record. A
+//! `conjure_error::Error` provides the most information, but any type implementing `std::error::Error` or [`LogError`]
+//! can be used, as can any type which derefs to one of those, like `Box<dyn Error + Send + Sync>` or
+//! `anyhow::Error`. Standard errors never report backtraces they captured internally, so they must be wrapped in
+//! [`Backtraced`] to include a backtrace.
 //!
 //! ## Examples
 //!
@@ -60,25 +79,103 @@
 //! }
 //! ```
 //!
+//! # Compile Time Filters
+//!
+//! Like the `log` crate, log levels can be statically disabled at compile time via Cargo features. Log invocations at
+//! disabled levels will be skipped and will not even be present in the resulting binary. The maximum level is
+//! configured separately for release and debug builds:
+//!
+//! * `max_level_off`
+//! * `max_level_fatal`
+//! * `max_level_error`
+//! * `max_level_warn`
+//! * `max_level_info`
+//! * `max_level_debug`
+//! * `max_level_trace`
+//! * `release_max_level_off`
+//! * `release_max_level_fatal`
+//! * `release_max_level_error`
+//! * `release_max_level_warn`
+//! * `release_max_level_info`
+//! * `release_max_level_debug`
+//! * `release_max_level_trace`
+//!
+//! If a `release_max_level_*` feature is set, it is used in release builds (i.e. when `debug_assertions` is off).
+//! Otherwise, the `max_level_*` feature is used. If multiple features are set, the most restrictive one wins. The
+//! resulting level is exposed as [`STATIC_MAX_LEVEL`]. As with the `log` crate, libraries should avoid setting these
+//! features - they are intended to be set by the final executable.
+//!
+//! ```toml
+//! [dependencies]
+//! witchcraft-log = { version = "5", features = ["max_level_debug", "release_max_level_info"] }
+//! ```
+//!
+//! # Tokio
+//!
+//! The MDC is thread local by default. The `tokio` Cargo feature enables the `tokio` module, which gives tasks spawned
+//! through it their own task local MDC, starting as a copy of the spawning task's.
+//!
+//! # Testing
+//!
+//! The `testing` Cargo feature enables the `testing` module, which can capture records logged during a test and make
+//! assertions about them.
+//!
+//! # Panics
+//!
+//! The `panic` module provides a panic hook which logs panics at the "fatal" level. The previously registered panic
+//! hook still runs afterwards, so panics continue to be written to standard error unless that hook is replaced first.
+//!
 //! # Bridging
 //!
 //! Even when an application is using `witchcraft-log`, many of its dependencies may still use the `log` crate. The
 //! `bridge` module provides functionality to forward records from the `log` crate to `witchcraft-log`.
+//!
+//! Conversely, the `forward` Cargo feature enables the `forward` module, which provides a logger forwarding records
+//! from `witchcraft-log` to the `log` crate, for use by applications which only configure the `log` crate.
+//!
+//! Similarly, the `tracing` Cargo feature enables the `tracing` module, which provides a `tracing-subscriber` layer
+//! forwarding events from the `tracing` crate to `witchcraft-log`, and the `slog` Cargo feature enables the `slog`
+//! module, which provides a `slog` drain forwarding records to `witchcraft-log`.
 #![warn(missing_docs)]
 
+// allows the derive macros to be used within the crate's own tests
+#[cfg(test)]
+extern crate self as witchcraft_log;
+
+pub use crate::error::*;
 pub use crate::level::*;
 pub use crate::logger::*;
+pub use crate::owned::*;
+pub use crate::params::*;
 pub use crate::record::*;
+pub use crate::safety::*;
+#[cfg(feature = "derive")]
+pub use witchcraft_log_derive::LogParams;
 
 pub mod bridge;
+mod error;
+#[cfg(feature = "forward")]
+pub mod forward;
 mod level;
 mod logger;
 #[macro_use]
 mod macros;
 pub mod mdc;
+mod owned;
+pub mod panic;
+mod params;
 #[doc(hidden)]
 pub mod private;
 mod record;
+mod safety;
+#[cfg(feature = "slog")]
+pub mod slog;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 
 #[cfg(test)]
 mod test;
@@ -23,6 +23,10 @@ pub trait Log: Sync + Send {
     ///
     /// This is used by the `enabled!` macro to allow callers to avoid expensive computation of log message parameters
     /// if the message would be discarded anyway.
+    ///
+    /// The logging macros cache the result of this method per call site, and skip records at call sites which are not
+    /// enabled. If the result of this method may change over time, `rebuild_interest_cache` must be called when it
+    /// does.
     fn enabled(&self, metadata: &Metadata<'_>) -> bool;
 
     /// Logs a `Record`.
@@ -33,6 +37,14 @@ pub trait Log: Sync + Send {
 
     /// Flushes any buffered records.
     fn flush(&self);
+
+    /// Returns the most verbose level that the logger may log.
+    ///
+    /// This is used by loggers which wrap other loggers to compute their own maximum level. Defaults to
+    /// `LevelFilter::Trace`.
+    fn max_level(&self) -> LevelFilter {
+        LevelFilter::Trace
+    }
 }
 
 struct NopLogger;
@@ -45,6 +57,10 @@ impl Log for NopLogger {
     fn log(&self, _: &Record<'_>) {}
 
     fn flush(&self) {}
+
+    fn max_level(&self) -> LevelFilter {
+        LevelFilter::Off
+    }
 }
 
 static LOGGER: OnceLock<&'static dyn Log> = OnceLock::new();
@@ -53,7 +69,9 @@ static LOGGER: OnceLock<&'static dyn Log> = OnceLock::new();
 ///
 /// The global logger can only be set once. Further calls will return an error.
 pub fn set_logger(logger: &'static dyn Log) -> Result<(), SetLoggerError> {
-    LOGGER.set(logger).map_err(|_| SetLoggerError(()))
+    LOGGER.set(logger).map_err(|_| SetLoggerError(()))?;
+    rebuild_interest_cache();
+    Ok(())
 }
 
 /// Sets the global logger to a `Box<dyn Log>`.
@@ -64,7 +82,10 @@ pub fn set_boxed_logger(logger: Box<dyn Log>) -> Result<(), SetLoggerError> {
     LOGGER.get_or_init(|| Box::leak(logger.take().unwrap()));
     match logger {
         Some(_) => Err(SetLoggerError(())),
-        None => Ok(()),
+        None => {
+            rebuild_interest_cache();
+            Ok(())
+        }
     }
 }
 
@@ -87,13 +108,44 @@ impl fmt::Display for SetLoggerError {
 
 impl Error for SetLoggerError {}
 
-static MAX_LOG_LEVEL_FILTER: AtomicUsize = AtomicUsize::new(0);
+// The low bits store the max level filter, and the high bits store the generation of the interest cache.
+static STATE: AtomicUsize = AtomicUsize::new(0);
+
+pub(crate) const LEVEL_BITS: u32 = 3;
+pub(crate) const LEVEL_MASK: usize = (1 << LEVEL_BITS) - 1;
+
+/// The statically configured maximum log level.
+///
+/// The log macros check this value before the dynamic `max_level()`, so messages logged at a higher level are removed
+/// entirely at compile time. It is controlled by the `max_level_*` and `release_max_level_*` Cargo features, and
+/// defaults to `LevelFilter::Trace`.
+pub const STATIC_MAX_LEVEL: LevelFilter = match cfg!(debug_assertions) {
+    false if cfg!(feature = "release_max_level_off") => LevelFilter::Off,
+    false if cfg!(feature = "release_max_level_fatal") => LevelFilter::Fatal,
+    false if cfg!(feature = "release_max_level_error") => LevelFilter::Error,
+    false if cfg!(feature = "release_max_level_warn") => LevelFilter::Warn,
+    false if cfg!(feature = "release_max_level_info") => LevelFilter::Info,
+    false if cfg!(feature = "release_max_level_debug") => LevelFilter::Debug,
+    false if cfg!(feature = "release_max_level_trace") => LevelFilter::Trace,
+    _ if cfg!(feature = "max_level_off") => LevelFilter::Off,
+    _ if cfg!(feature = "max_level_fatal") => LevelFilter::Fatal,
+    _ if cfg!(feature = "max_level_error") => LevelFilter::Error,
+    _ if cfg!(feature = "max_level_warn") => LevelFilter::Warn,
+    _ if cfg!(feature = "max_level_info") => LevelFilter::Info,
+    _ if cfg!(feature = "max_level_debug") => LevelFilter::Debug,
+    _ => LevelFilter::Trace,
+};
 
 /// Sets the global maximum log level.
 ///
-/// Generally, this should only be called by the logging implementation.
+/// Generally, this should only be called by the logging implementation. This also rebuilds the interest cache, and
+/// updates the `log` crate's max level if the bridge was installed with `bridge::init`.
 pub fn set_max_level(level: LevelFilter) {
-    MAX_LOG_LEVEL_FILTER.store(level as usize, Ordering::Relaxed);
+    let _ = STATE.fetch_update(Ordering::Release, Ordering::Relaxed, |state| {
+        let generation = (state >> LEVEL_BITS).wrapping_add(1);
+        Some((generation << LEVEL_BITS) | level as usize)
+    });
+    crate::bridge::sync_max_level(level);
 }
 
 /// Returns the current maximum log level.
@@ -102,5 +154,20 @@ pub fn set_max_level(level: LevelFilter) {
 /// level is set by the `set_max_level` function.
 #[inline(always)]
 pub fn max_level() -> LevelFilter {
-    unsafe { mem::transmute(MAX_LOG_LEVEL_FILTER.load(Ordering::Relaxed)) }
+    unsafe { mem::transmute(STATE.load(Ordering::Relaxed) & LEVEL_MASK) }
+}
+
+/// Invalidates the cached enabled state of all log call sites.
+///
+/// The logging macros cache the result of `Log::enabled` per call site. Loggers must call this function whenever the
+/// set of enabled targets changes, for example when their filter is reconfigured. It is called automatically by
+/// `set_logger`, `set_boxed_logger`, and `set_max_level`.
+pub fn rebuild_interest_cache() {
+    STATE.fetch_add(1 << LEVEL_BITS, Ordering::Release);
+}
+
+/// Returns the combined max level and interest cache generation.
+#[inline(always)]
+pub(crate) fn state() -> usize {
+    STATE.load(Ordering::Relaxed)
 }
@@ -13,11 +13,92 @@
 // limitations under the License.
 
 /// Logs a message at the specified level.
+///
+/// A message can be rate limited per call site by starting the arguments with `every: <Duration>`. Repeated messages
+/// within the period are discarded, and the next message logged after the period has elapsed will include a
+/// `suppressedCount` safe parameter with the number of messages discarded since the last one was logged.
+///
+/// Parameter values prefixed with `lazy` are closures which are only called if the record is actually serialized by
+/// the logger, so expensive values do not need to be guarded by a check of [`enabled!`](crate::enabled). The closure is
+/// called at most once per record.
+///
+/// The `params` section includes the parameters of a value implementing [`LogParams`](crate::LogParams), in addition
+/// to those in the `safe` and `unsafe` sections.
+///
+/// The `error` section accepts a value implementing [`LogError`](crate::LogError) or `std::error::Error`, or a value
+/// which derefs to one of those, such as `Box<dyn Error + Send + Sync>` or `anyhow::Error`. Other values fail to
+/// compile with an error about a missing `log_error` method on `ErrorWrap`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use witchcraft_log::Level;
+///
+/// # let (retries, user) = (1, "");
+/// witchcraft_log::log!(Level::Info, "message");
+/// witchcraft_log::log!(Level::Info, "message", safe: { retries: retries }, unsafe: { user: user });
+/// witchcraft_log::log!(Level::Warn, every: Duration::from_secs(10), "message", safe: { retries: retries });
+/// witchcraft_log::log!(Level::Debug, "message", unsafe: { dump: lazy || format!("{user:?}") });
+/// witchcraft_log::log!(Level::Info, "message", tags: { stage: "startup" });
+/// # let error: Box<dyn std::error::Error + Send + Sync> = "error".into();
+/// witchcraft_log::log!(Level::Error, "message", error: error);
+/// ```
+#[cfg_attr(
+    feature = "derive",
+    doc = r#"
+```
+use witchcraft_log::Level;
+
+# let retries = 1;
+#[derive(witchcraft_log::LogParams)]
+struct Request {
+    #[safe]
+    retries: u32,
+}
+
+let request = Request { retries: 1 };
+witchcraft_log::log!(Level::Info, "message", safe: { retries: retries }, params: request);
+```
+"#
+)]
 #[macro_export]
 macro_rules! log {
+    (
+        $lvl:expr,
+        every: $period:expr,
+        $msg:expr
+        $(, safe: { $($safe:tt)* })?
+        $(, unsafe: { $($unsafe:tt)* })?
+        $(, params: $params:expr)?
+        $(, tags: { $($tag_key:ident: $tag_value:expr),* $(,)? })?
+        $(, error: $error:expr)?
+        $(,)?
+    ) => {{
+        static CALLSITE: $crate::private::Callsite = $crate::private::Callsite::new();
+        static RATE_LIMITER: $crate::private::RateLimiter = $crate::private::RateLimiter::new();
+        let level = $lvl;
+        if level <= $crate::STATIC_MAX_LEVEL && CALLSITE.enabled(level, module_path!()) {
+            if let Some(suppressed) = RATE_LIMITER.check($period) {
+                $crate::private::log(
+                    level,
+                    &(module_path!(), file!(), line!(), $msg),
+                    $crate::private::skip_suppressed(
+                        &$crate::__params!(@ safe_param [("suppressedCount", &suppressed),] $($($safe)*)?),
+                        suppressed,
+                    ),
+                    &$crate::__params!(@ unsafe_param [] $($($unsafe)*)?),
+                    &[$(&$params as &dyn $crate::LogParams)?],
+                    &[$($((stringify!($tag_key), &$tag_value)),*)*],
+                    None $(.or(Some(&$crate::private::ErrorWrap::new(&$error).log_error() as &dyn $crate::LogError)))?,
+                );
+            }
+        }
+    }};
     ($lvl:expr, $msg:expr) => {{
+        static CALLSITE: $crate::private::Callsite = $crate::private::Callsite::new();
         let level = $lvl;
-        if level <= $crate::max_level() {
+        if level <= $crate::STATIC_MAX_LEVEL && CALLSITE.enabled(level, module_path!()) {
             $crate::private::log_minimal(
                 level,
                 &(module_path!(), file!(), line!(), $msg),
@@ -27,24 +108,95 @@ macro_rules! log {
     (
         $lvl:expr,
         $msg:expr
-        $(, safe: { $($safe_key:ident: $safe_value:expr),* $(,)? })?
-        $(, unsafe: { $($unsafe_key:ident: $unsafe_value:expr),* $(,)? })?
+        $(, safe: { $($safe:tt)* })?
+        $(, unsafe: { $($unsafe:tt)* })?
+        $(, params: $params:expr)?
+        $(, tags: { $($tag_key:ident: $tag_value:expr),* $(,)? })?
         $(, error: $error:expr)?
         $(,)?
     ) => {{
+        static CALLSITE: $crate::private::Callsite = $crate::private::Callsite::new();
         let level = $lvl;
-        if level <= $crate::max_level() {
+        if level <= $crate::STATIC_MAX_LEVEL && CALLSITE.enabled(level, module_path!()) {
             $crate::private::log(
                 level,
                 &(module_path!(), file!(), line!(), $msg),
-                &[$($((stringify!($safe_key), &$safe_value)),*)*],
-                &[$($((stringify!($unsafe_key), &$unsafe_value)),*)*],
-                None $(.or(Some(&$error)))?,
+                &$crate::__params!(@ safe_param [] $($($safe)*)?),
+                &$crate::__params!(@ unsafe_param [] $($($unsafe)*)?),
+                &[$(&$params as &dyn $crate::LogParams)?],
+                &[$($((stringify!($tag_key), &$tag_value)),*)*],
+                None $(.or(Some(&$crate::private::ErrorWrap::new(&$error).log_error() as &dyn $crate::LogError)))?,
             );
         }
     }};
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __params {
+    (@ $check:ident [$($out:tt)*]) => {
+        [$($out)*]
+    };
+    (@ $check:ident [$($out:tt)*] $key:ident: lazy $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__params!(
+            @ $check [
+                $($out)*
+                (stringify!($key), $crate::private::$check(&$crate::private::Lazy::new($value))),
+            ] $($($rest)*)?
+        )
+    };
+    (@ $check:ident [$($out:tt)*] $key:ident: $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__params!(
+            @ $check [$($out)* (stringify!($key), $crate::private::$check(&$value)),] $($($rest)*)?
+        )
+    };
+}
+
+/// Runs a block with entries added to the MDC.
+///
+/// The MDC is reset to its previous state once the block completes. The `safe`, `unsafe`, and `tags` sections are all
+/// optional, but must appear in that order. The block should not contain `.await` points, since the MDC is not
+/// maintained across them - use [`mdc::bind`](crate::mdc::bind) for futures.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::mdc;
+///
+/// # let (request_id, user) = (0, "");
+/// mdc::with!(safe: { requestId: request_id }, unsafe: { user: user }, tags: { stage: "startup" }, {
+///     witchcraft_log::info!("handling request");
+/// });
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mdc_with {
+    (
+        $(safe: { $($safe_key:ident: $safe_value:expr),* $(,)? },)?
+        $(unsafe: { $($unsafe_key:ident: $unsafe_value:expr),* $(,)? },)?
+        $(tags: { $($tag_key:ident: $tag_value:expr),* $(,)? },)?
+        $body:block
+    ) => {{
+        let _guard = $crate::mdc::scope();
+        $($(
+            $crate::mdc::insert_safe(
+                stringify!($safe_key),
+                $crate::private::safe_param(&$safe_value),
+            );
+        )*)?
+        $($(
+            $crate::mdc::insert_unsafe(
+                stringify!($unsafe_key),
+                $crate::private::unsafe_param(&$unsafe_value),
+            );
+        )*)?
+        $($(
+            $crate::mdc::insert_tag(stringify!($tag_key), $tag_value);
+        )*)?
+        $body
+    }};
+}
+
 /// Logs a message at the "fatal" level.
 #[macro_export]
 macro_rules! fatal {
@@ -93,11 +245,78 @@ macro_rules! trace {
     }
 }
 
+/// Logs a message at the specified level only the first time the call site is reached.
+///
+/// Takes the same arguments as [`log!`](crate::log).
+#[macro_export]
+macro_rules! log_once {
+    ($lvl:expr, $($v:tt)*) => {{
+        static CALLSITE: $crate::private::Callsite = $crate::private::Callsite::new();
+        static ONCE: $crate::private::Once = $crate::private::Once::new();
+        let level = $lvl;
+        if level <= $crate::STATIC_MAX_LEVEL
+            && CALLSITE.enabled(level, module_path!())
+            && ONCE.check()
+        {
+            $crate::log!(level, $($v)*);
+        }
+    }};
+}
+
+/// Logs a message at the "fatal" level only the first time the call site is reached.
+#[macro_export]
+macro_rules! fatal_once {
+    ($($v:tt)*) => {
+        $crate::log_once!($crate::Level::Fatal, $($v)*)
+    }
+}
+
+/// Logs a message at the "error" level only the first time the call site is reached.
+#[macro_export]
+macro_rules! error_once {
+    ($($v:tt)*) => {
+        $crate::log_once!($crate::Level::Error, $($v)*)
+    }
+}
+
+/// Logs a message at the "warn" level only the first time the call site is reached.
+#[macro_export]
+macro_rules! warn_once {
+    ($($v:tt)*) => {
+        $crate::log_once!($crate::Level::Warn, $($v)*)
+    }
+}
+
+/// Logs a message at the "info" level only the first time the call site is reached.
+#[macro_export]
+macro_rules! info_once {
+    ($($v:tt)*) => {
+        $crate::log_once!($crate::Level::Info, $($v)*)
+    }
+}
+
+/// Logs a message at the "debug" level only the first time the call site is reached.
+#[macro_export]
+macro_rules! debug_once {
+    ($($v:tt)*) => {
+        $crate::log_once!($crate::Level::Debug, $($v)*)
+    }
+}
+
+/// Logs a message at the "trace" level only the first time the call site is reached.
+#[macro_export]
+macro_rules! trace_once {
+    ($($v:tt)*) => {
+        $crate::log_once!($crate::Level::Trace, $($v)*)
+    }
+}
+
 /// Determines if a message logged at the specified level in the same module would be logged or not.
 #[macro_export]
 macro_rules! enabled {
     ($lvl:expr) => {{
+        static CALLSITE: $crate::private::Callsite = $crate::private::Callsite::new();
         let level = $lvl;
-        level <= $crate::max_level() && $crate::private::enabled(level, module_path!())
+        level <= $crate::STATIC_MAX_LEVEL && CALLSITE.enabled(level, module_path!())
     }};
 }
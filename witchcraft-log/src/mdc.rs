@@ -13,25 +13,69 @@
 // limitations under the License.
 //! A Mapped Diagnostic Context (MDC) for Witchcraft loggers.
 //!
-//! An MDC is a thread local map containing extra parameters. Witchcraft logging implementations should include the
-//! contents of the MDC in service logs.
+//! An MDC is a thread local map containing extra parameters and tags. Witchcraft logging implementations should include
+//! the contents of the MDC in service logs.
+//!
+//! With the `tokio` Cargo feature enabled, the `tokio` module can additionally give each task its own MDC, which is
+//! used in place of the thread local one while the task runs.
+//!
+//! Since the MDC is thread local, it has to be explicitly carried along with work that moves between threads. The
+//! [`bind`] and [`bind_stream`] adapters maintain the MDC across polls of futures and streams, and [`bind_fn`] and
+//! [`spawn`] propagate it to closures run on other threads.
+use crate::private::SafeKeyValue;
 use conjure_object::Any;
+use futures_core::Stream;
+use imbl::hashmap;
+use imbl::shared_ptr::DefaultSharedPtr;
+use imbl::HashMap;
 use pin_project::{pin_project, pinned_drop};
-use serde::Serialize;
+use serde::de::{DeserializeOwned, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::{hash_map, HashMap};
 use std::future::Future;
-use std::mem;
+use std::marker::PhantomData;
 use std::pin::Pin;
-use std::sync::{Arc, OnceLock};
+use std::sync::OnceLock;
 use std::task::{Context, Poll};
+use std::thread::{self, JoinHandle};
+use std::{fmt, mem};
+
+#[doc(inline)]
+pub use crate::__mdc_with as with;
 
 static EMPTY: OnceLock<Map> = OnceLock::new();
+static EMPTY_TAGS: OnceLock<Tags> = OnceLock::new();
 
 thread_local! {
     static MDC: RefCell<Snapshot> = RefCell::new(Snapshot::new());
 }
 
+#[cfg(feature = "tokio")]
+tokio::task_local! {
+    pub(crate) static TASK_MDC: RefCell<Snapshot>;
+}
+
+/// Runs a closure with the active MDC.
+///
+/// This is the task local MDC when running within a task set up by the `tokio` module, and the thread local MDC
+/// otherwise.
+fn with_mdc<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut Snapshot) -> R,
+{
+    #[cfg(feature = "tokio")]
+    let f = {
+        let mut f = Some(f);
+        if let Ok(r) = TASK_MDC.try_with(|v| (f.take().unwrap())(&mut v.borrow_mut())) {
+            return r;
+        }
+        f.unwrap()
+    };
+
+    MDC.with(|v| f(&mut v.borrow_mut()))
+}
+
 /// Inserts a new safe parameter into the MDC.
 ///
 /// # Panics
@@ -41,7 +85,7 @@ pub fn insert_safe<T>(key: &'static str, value: T) -> Option<Any>
 where
     T: Serialize,
 {
-    MDC.with(|v| v.borrow_mut().safe_mut().insert(key, value))
+    with_mdc(|mdc| mdc.safe_mut().insert(key, value))
 }
 
 /// Inserts a new unsafe parameter into the MDC.
@@ -53,43 +97,56 @@ pub fn insert_unsafe<T>(key: &'static str, value: T) -> Option<Any>
 where
     T: Serialize,
 {
-    MDC.with(|v| v.borrow_mut().unsafe_mut().insert(key, value))
+    with_mdc(|mdc| mdc.unsafe_mut().insert(key, value))
 }
 
 /// Removes the specified safe parameter from the MDC.
 pub fn remove_safe(key: &str) -> Option<Any> {
-    MDC.with(|v| v.borrow_mut().safe_mut().remove(key))
+    with_mdc(|mdc| mdc.safe_mut().remove(key))
 }
 
 /// Removes the specified unsafe parameter from the MDC.
 pub fn remove_unsafe(key: &str) -> Option<Any> {
-    MDC.with(|v| v.borrow_mut().unsafe_mut().remove(key))
+    with_mdc(|mdc| mdc.unsafe_mut().remove(key))
+}
+
+/// Inserts a new tag into the MDC.
+pub fn insert_tag<T>(key: &'static str, value: T) -> Option<String>
+where
+    T: Into<String>,
+{
+    with_mdc(|mdc| mdc.tags_mut().insert(key, value))
+}
+
+/// Removes the specified tag from the MDC.
+pub fn remove_tag(key: &str) -> Option<String> {
+    with_mdc(|mdc| mdc.tags_mut().remove(key))
 }
 
 /// Takes a snapshot of the MDC.
 ///
 /// The snapshot and MDC are not connected - updates to the snapshot will not affect the MDC and vice versa.
 pub fn snapshot() -> Snapshot {
-    MDC.with(|v| v.borrow().clone())
+    with_mdc(|mdc| mdc.clone())
 }
 
 /// Clears the contents of the MDC.
 pub fn clear() {
-    MDC.with(|v| {
-        let mut mdc = v.borrow_mut();
+    with_mdc(|mdc| {
         mdc.safe_mut().clear();
         mdc.unsafe_mut().clear();
+        mdc.tags_mut().clear();
     });
 }
 
 /// Overwrites the MDC with a snapshot, returning the previous state.
 pub fn set(snapshot: Snapshot) -> Snapshot {
-    MDC.with(|v| mem::replace(&mut *v.borrow_mut(), snapshot))
+    with_mdc(|mdc| mem::replace(mdc, snapshot))
 }
 
 /// Swaps the MDC with a snapshot in-place.
 pub fn swap(snapshot: &mut Snapshot) {
-    MDC.with(|v| mem::swap(&mut *v.borrow_mut(), snapshot));
+    with_mdc(|mdc| mem::swap(mdc, snapshot));
 }
 
 /// Wraps a future with a layer that maintains the MDC across polls.
@@ -103,15 +160,188 @@ pub fn bind<F>(future: F) -> Bind<F> {
     }
 }
 
+/// Wraps a stream with a layer that maintains the MDC across polls.
+///
+/// This is the [`Stream`] equivalent of [`bind`].
+pub fn bind_stream<S>(stream: S) -> BindStream<S> {
+    BindStream {
+        stream: Some(stream),
+        snapshot: snapshot(),
+    }
+}
+
+/// Wraps a closure with a layer that runs it with the current MDC.
+///
+/// The closure will execute with the MDC state at the time this function is called, regardless of the thread it runs
+/// on, and the MDC of that thread will be restored once it returns. This can be used to propagate the MDC to work
+/// submitted to thread pools or other executors.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::mdc;
+///
+/// mdc::insert_safe("requestId", "abc");
+/// let work = mdc::bind_fn(|| witchcraft_log::info!("shaving yak"));
+/// std::thread::spawn(work).join().unwrap();
+/// ```
+pub fn bind_fn<F, R>(f: F) -> impl FnOnce() -> R
+where
+    F: FnOnce() -> R,
+{
+    let mut snapshot = snapshot();
+    move || {
+        let _guard = scope_with(&mut snapshot);
+        f()
+    }
+}
+
+/// Spawns a new thread which runs a closure with the current MDC.
+///
+/// This is equivalent to `std::thread::spawn(mdc::bind_fn(f))`.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    thread::spawn(bind_fn(f))
+}
+
 /// Creates a guard object which will reset the MDC to the state it was previously in on drop.
 pub fn scope() -> Scope {
     Scope { old: snapshot() }
 }
 
+/// A typed MDC key.
+///
+/// A key fixes both the type of its values and whether they are stored as safe or unsafe parameters, so they can't be
+/// mixed up at its uses. Values are stored as normal MDC entries under the key's name and are deserialized back into
+/// the key's type when read.
+///
+/// With the `strict_safety` Cargo feature enabled, the type of a key created with [`Key::safe`] must implement
+/// [`SafeArg`](crate::SafeArg). Keys of other types whose values are all known to be safe, like identifiers, can
+/// instead be created with [`Key::assume_safe`].
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::mdc::{self, Key};
+///
+/// const ATTEMPT: Key<u32> = Key::safe("attempt");
+///
+/// let _guard = mdc::scope();
+/// ATTEMPT.insert(2);
+/// assert_eq!(ATTEMPT.get(), Some(2));
+/// ```
+pub struct Key<T> {
+    name: &'static str,
+    safe: bool,
+    _p: PhantomData<fn(T) -> T>,
+}
+
+impl<T> Clone for Key<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Key<T> {}
+
+impl<T> fmt::Debug for Key<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Key")
+            .field("name", &self.name)
+            .field("safe", &self.safe)
+            .finish()
+    }
+}
+
+impl<T> Key<T> {
+    /// Creates a key whose values are stored as safe parameters.
+    ///
+    /// With the `strict_safety` Cargo feature enabled, the key's type must implement [`SafeArg`](crate::SafeArg).
+    #[inline]
+    pub const fn safe(name: &'static str) -> Self
+    where
+        T: SafeKeyValue,
+    {
+        Key::assume_safe(name)
+    }
+
+    /// Creates a key whose values are stored as safe parameters, regardless of its type.
+    ///
+    /// This asserts that all values of the key are safe to log, bypassing the checks made with the `strict_safety`
+    /// Cargo feature enabled, so it should only be used for types whose values are known to be safe.
+    #[inline]
+    pub const fn assume_safe(name: &'static str) -> Self {
+        Key {
+            name,
+            safe: true,
+            _p: PhantomData,
+        }
+    }
+
+    /// Creates a key whose values are stored as unsafe parameters.
+    #[inline]
+    pub const fn unsafe_(name: &'static str) -> Self {
+        Key {
+            name,
+            safe: false,
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns the key's name.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Determines if the key's values are stored as safe parameters.
+    #[inline]
+    pub fn is_safe(&self) -> bool {
+        self.safe
+    }
+
+    /// Inserts a value for the key into the MDC, returning the old value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value cannot be serialized into an [`Any`].
+    pub fn insert(self, value: T) -> Option<T>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        with_mdc(|mdc| mdc.insert(self, value))
+    }
+
+    /// Looks up the value of the key in the MDC.
+    ///
+    /// Returns `None` if the key is not present or its value cannot be deserialized into the key's type.
+    pub fn get(self) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        with_mdc(|mdc| mdc.get(self))
+    }
+
+    /// Removes the key from the MDC, returning its value.
+    pub fn remove(self) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        with_mdc(|mdc| mdc.remove(self))
+    }
+}
+
 /// A map of MDC entries.
+///
+/// The map is persistent, so clones share structure with each other. Cloning a map is cheap, and modifying a clone
+/// only copies the parts of the map being modified.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Map {
-    map: Arc<HashMap<&'static str, Any>>,
+    map: HashMap<Cow<'static, str>, Any>,
 }
 
 impl Default for Map {
@@ -119,7 +349,7 @@ impl Default for Map {
     fn default() -> Self {
         EMPTY
             .get_or_init(|| Map {
-                map: Arc::new(HashMap::new()),
+                map: HashMap::new(),
             })
             .clone()
     }
@@ -135,11 +365,7 @@ impl Map {
     /// Removes all entries from the map.
     #[inline]
     pub fn clear(&mut self) {
-        // try to preserve capacity if we're the unique owner
-        match Arc::get_mut(&mut self.map) {
-            Some(map) => map.clear(),
-            None => *self = Map::new(),
-        }
+        *self = Map::new();
     }
 
     /// Returns the number of entries in the map.
@@ -177,13 +403,13 @@ impl Map {
         V: Serialize,
     {
         let value = Any::new(value).expect("value failed to serialize");
-        Arc::make_mut(&mut self.map).insert(key, value)
+        self.map.insert(Cow::Borrowed(key), value)
     }
 
     /// Removes an entry from the map, returning its value.
     #[inline]
     pub fn remove(&mut self, key: &str) -> Option<Any> {
-        Arc::make_mut(&mut self.map).remove(key)
+        self.map.remove(key)
     }
 
     /// Returns an iterator over the entries in the map.
@@ -195,8 +421,48 @@ impl Map {
     }
 }
 
+impl Serialize for Map {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Map {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MapVisitor;
+
+        impl<'de> Visitor<'de> for MapVisitor {
+            type Value = Map;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = HashMap::new();
+                while let Some((key, value)) = map.next_entry::<String, Any>()? {
+                    entries.insert(Cow::Owned(key), value);
+                }
+
+                Ok(Map { map: entries })
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor)
+    }
+}
+
 impl<'a> IntoIterator for &'a Map {
-    type Item = (&'static str, &'a Any);
+    type Item = (&'a str, &'a Any);
 
     type IntoIter = Iter<'a>;
 
@@ -208,15 +474,15 @@ impl<'a> IntoIterator for &'a Map {
 
 /// An iterator over the entries in a [`Map`].
 pub struct Iter<'a> {
-    it: hash_map::Iter<'a, &'static str, Any>,
+    it: hashmap::Iter<'a, Cow<'static, str>, Any, DefaultSharedPtr>,
 }
 
 impl<'a> Iterator for Iter<'a> {
-    type Item = (&'static str, &'a Any);
+    type Item = (&'a str, &'a Any);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.it.next().map(|(k, v)| (*k, v))
+        self.it.next().map(|(k, v)| (&**k, v))
     }
 
     #[inline]
@@ -232,11 +498,172 @@ impl ExactSizeIterator for Iter<'_> {
     }
 }
 
+/// A map of MDC tags.
+///
+/// Like [`Map`], the map is persistent, so clones share structure with each other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tags {
+    map: HashMap<Cow<'static, str>, String>,
+}
+
+impl Default for Tags {
+    #[inline]
+    fn default() -> Self {
+        EMPTY_TAGS
+            .get_or_init(|| Tags {
+                map: HashMap::new(),
+            })
+            .clone()
+    }
+}
+
+impl Tags {
+    /// Returns a new, empty map.
+    #[inline]
+    pub fn new() -> Self {
+        Tags::default()
+    }
+
+    /// Removes all tags from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Tags::new();
+    }
+
+    /// Returns the number of tags in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Determines if the map is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Looks up a tag in the map.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.map.get(key).map(|v| &**v)
+    }
+
+    /// Determines if the map contains the specified tag.
+    #[inline]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Inserts a new tag into the map, returning the old value corresponding to the key.
+    #[inline]
+    pub fn insert<V>(&mut self, key: &'static str, value: V) -> Option<String>
+    where
+        V: Into<String>,
+    {
+        self.map.insert(Cow::Borrowed(key), value.into())
+    }
+
+    /// Removes a tag from the map, returning its value.
+    #[inline]
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.map.remove(key)
+    }
+
+    /// Returns an iterator over the tags in the map.
+    #[inline]
+    pub fn iter(&self) -> TagsIter<'_> {
+        TagsIter {
+            it: self.map.iter(),
+        }
+    }
+}
+
+impl Serialize for Tags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Tags {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TagsVisitor;
+
+        impl<'de> Visitor<'de> for TagsVisitor {
+            type Value = Tags;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = HashMap::new();
+                while let Some((key, value)) = map.next_entry::<String, String>()? {
+                    entries.insert(Cow::Owned(key), value);
+                }
+
+                Ok(Tags { map: entries })
+            }
+        }
+
+        deserializer.deserialize_map(TagsVisitor)
+    }
+}
+
+impl<'a> IntoIterator for &'a Tags {
+    type Item = (&'a str, &'a str);
+
+    type IntoIter = TagsIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries in a [`Tags`] map.
+pub struct TagsIter<'a> {
+    it: hashmap::Iter<'a, Cow<'static, str>, String, DefaultSharedPtr>,
+}
+
+impl<'a> Iterator for TagsIter<'a> {
+    type Item = (&'a str, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.it.next().map(|(k, v)| (&**k, &**v))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl ExactSizeIterator for TagsIter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.it.len()
+    }
+}
+
 /// A portable snapshot of the MDC.
-#[derive(Clone, Default, Debug, PartialEq, Eq)]
+#[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Snapshot {
+    #[serde(default)]
     safe: Map,
+    #[serde(default, rename = "unsafe")]
     unsafe_: Map,
+    #[serde(default)]
+    tags: Tags,
 }
 
 impl Snapshot {
@@ -269,6 +696,67 @@ impl Snapshot {
     pub fn unsafe_mut(&mut self) -> &mut Map {
         &mut self.unsafe_
     }
+
+    /// Returns a shared reference to the tags in the snapshot.
+    #[inline]
+    pub fn tags(&self) -> &Tags {
+        &self.tags
+    }
+
+    /// Returns a mutable reference to the tags in the snapshot.
+    #[inline]
+    pub fn tags_mut(&mut self) -> &mut Tags {
+        &mut self.tags
+    }
+
+    /// Looks up the value of a typed key in the snapshot.
+    ///
+    /// Returns `None` if the key is not present or its value cannot be deserialized into the key's type.
+    pub fn get<T>(&self, key: Key<T>) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.map(key).get(key.name)?.clone().deserialize_into().ok()
+    }
+
+    /// Inserts a value for a typed key into the snapshot, returning the old value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value cannot be serialized into an [`Any`].
+    pub fn insert<T>(&mut self, key: Key<T>, value: T) -> Option<T>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        self.map_mut(key)
+            .insert(key.name, value)?
+            .deserialize_into()
+            .ok()
+    }
+
+    /// Removes a typed key from the snapshot, returning its value.
+    pub fn remove<T>(&mut self, key: Key<T>) -> Option<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.map_mut(key).remove(key.name)?.deserialize_into().ok()
+    }
+
+    fn map<T>(&self, key: Key<T>) -> &Map {
+        if key.safe {
+            &self.safe
+        } else {
+            &self.unsafe_
+        }
+    }
+
+    fn map_mut<T>(&mut self, key: Key<T>) -> &mut Map {
+        if key.safe {
+            &mut self.safe
+        } else {
+            &mut self.unsafe_
+        }
+    }
 }
 
 /// A guard object which resets the MDC to an earlier state when it drops.
@@ -312,6 +800,40 @@ where
     }
 }
 
+/// A stream which manages the MDC across polls to a delegate.
+#[pin_project(PinnedDrop)]
+pub struct BindStream<S> {
+    #[pin]
+    stream: Option<S>,
+    snapshot: Snapshot,
+}
+
+#[pinned_drop]
+impl<S> PinnedDrop for BindStream<S> {
+    fn drop(self: Pin<&mut Self>) {
+        let mut this = self.project();
+        let _guard = scope_with(this.snapshot);
+        this.stream.set(None);
+    }
+}
+
+impl<S> Stream for BindStream<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let _guard = scope_with(this.snapshot);
+        this.stream.as_pin_mut().unwrap().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.as_ref().unwrap().size_hint()
+    }
+}
+
 /// Sets the MDC state to the snapshot, resetting it to the state it was previously on drop.
 fn scope_with(snapshot: &mut Snapshot) -> ScopeWith<'_> {
     swap(snapshot);
@@ -331,6 +853,8 @@ impl Drop for ScopeWith<'_> {
 #[cfg(test)]
 mod test {
     use conjure_object::Any;
+    use futures_util::{stream, StreamExt};
+    use std::thread;
 
     use crate::mdc;
 
@@ -371,4 +895,132 @@ mod test {
             &Any::new("bar").unwrap(),
         );
     }
+
+    // logs strings as safe parameters
+    #[cfg(not(feature = "strict_safety"))]
+    #[test]
+    fn with() {
+        mdc::clear();
+
+        mdc::insert_safe("foo", "bar");
+        let value = mdc::with!(safe: { foo: "baz", count: 2 }, tags: { stage: "startup" }, {
+            let snapshot = mdc::snapshot();
+            assert_eq!(snapshot.safe().get("foo").unwrap(), &Any::new("baz").unwrap());
+            assert_eq!(snapshot.safe().get("count").unwrap(), &Any::new(2).unwrap());
+            assert_eq!(snapshot.tags().get("stage"), Some("startup"));
+            1
+        });
+        assert_eq!(value, 1);
+
+        let snapshot = mdc::snapshot();
+        assert_eq!(
+            snapshot.safe().get("foo").unwrap(),
+            &Any::new("bar").unwrap()
+        );
+        assert!(!snapshot.safe().contains_key("count"));
+        assert!(!snapshot.tags().contains_key("stage"));
+    }
+
+    #[test]
+    fn bind_fn() {
+        mdc::clear();
+
+        mdc::insert_unsafe("foo", "bar");
+        let f = mdc::bind_fn(|| {
+            mdc::insert_unsafe("foo", "baz");
+            mdc::snapshot().unsafe_().get("foo").cloned()
+        });
+        mdc::insert_unsafe("foo", "qux");
+
+        assert_eq!(
+            thread::spawn(f).join().unwrap().unwrap(),
+            Any::new("baz").unwrap(),
+        );
+        assert_eq!(mdc::spawn(mdc::snapshot).join().unwrap(), mdc::snapshot(),);
+        assert_eq!(
+            mdc::snapshot().unsafe_().get("foo").unwrap(),
+            &Any::new("qux").unwrap(),
+        );
+    }
+
+    #[test]
+    fn bind_stream() {
+        mdc::clear();
+
+        mdc::insert_safe("foo", "bar");
+        let stream = mdc::bind_stream(stream::iter(0..2usize).map(|i| {
+            mdc::insert_safe("count", i);
+            mdc::snapshot()
+        }));
+        mdc::clear();
+
+        let snapshots = futures_executor::block_on(stream.collect::<Vec<_>>());
+        assert_eq!(snapshots.len(), 2);
+        for (i, snapshot) in snapshots.iter().enumerate() {
+            assert_eq!(
+                snapshot.safe().get("foo").unwrap(),
+                &Any::new("bar").unwrap()
+            );
+            assert_eq!(snapshot.safe().get("count").unwrap(), &Any::new(i).unwrap());
+        }
+        assert!(mdc::snapshot().safe().is_empty());
+    }
+
+    #[test]
+    fn typed_keys() {
+        const ATTEMPT: mdc::Key<u32> = mdc::Key::safe("attempt");
+        const USER: mdc::Key<String> = mdc::Key::unsafe_("user");
+
+        mdc::clear();
+
+        assert_eq!(ATTEMPT.insert(1), None);
+        assert_eq!(ATTEMPT.insert(2), Some(1));
+        assert_eq!(USER.insert("bob".to_string()), None);
+        assert_eq!(ATTEMPT.get(), Some(2));
+        assert_eq!(USER.get(), Some("bob".to_string()));
+
+        let snapshot = mdc::snapshot();
+        assert_eq!(
+            snapshot.safe().get("attempt").unwrap(),
+            &Any::new(2u32).unwrap()
+        );
+        assert_eq!(
+            snapshot.unsafe_().get("user").unwrap(),
+            &Any::new("bob").unwrap()
+        );
+        assert_eq!(snapshot.get(ATTEMPT), Some(2));
+
+        mdc::insert_safe("attempt", "two");
+        assert_eq!(ATTEMPT.get(), None);
+        assert_eq!(USER.remove(), Some("bob".to_string()));
+        assert_eq!(USER.get(), None);
+    }
+
+    #[test]
+    fn persistent_map() {
+        let keys = (0..100)
+            .map(|i| &*format!("key{i}").leak())
+            .collect::<Vec<_>>();
+
+        let mut map = mdc::Map::new();
+        for (i, key) in keys.iter().enumerate() {
+            map.insert(key, i);
+        }
+
+        let snapshot = map.clone();
+        map.insert(keys[0], "updated");
+        map.remove(keys[1]);
+
+        assert_eq!(snapshot.len(), 100);
+        assert_eq!(snapshot.get(keys[0]).unwrap(), &Any::new(0usize).unwrap());
+        assert!(snapshot.contains_key(keys[1]));
+        assert_eq!(map.len(), 99);
+        assert_eq!(map.get(keys[0]).unwrap(), &Any::new("updated").unwrap());
+        assert!(!map.contains_key(keys[1]));
+        assert_eq!(map.iter().len(), 99);
+
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(snapshot.len(), 100);
+    }
 }
@@ -0,0 +1,354 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::mdc::Snapshot;
+use crate::{Level, LogError, Record};
+use conjure_error::SerializableError;
+use conjure_object::{Any, DateTime, Utc};
+use erased_serde::Serialize as ErasedSerialize;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::thread::ThreadId;
+
+/// An owned log record.
+///
+/// Unlike [`Record`], an `OwnedRecord` does not borrow any of its contents, so it can be stored, sent to another
+/// thread, or serialized for later processing. It is created by [`Record::to_owned`].
+///
+/// The thread ID is not serialized.
+///
+/// Parameters whose values fail to serialize are omitted from the owned record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnedRecord {
+    level: Level,
+    target: String,
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    line: Option<u32>,
+    message: String,
+    #[serde(default, with = "params")]
+    safe_params: Vec<(String, Any)>,
+    #[serde(default, with = "params")]
+    unsafe_params: Vec<(String, Any)>,
+    #[serde(default, with = "params")]
+    tags: Vec<(String, String)>,
+    #[serde(default)]
+    error: Option<OwnedError>,
+    #[serde(default)]
+    timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    thread_name: Option<String>,
+    #[serde(skip)]
+    thread_id: Option<ThreadId>,
+    #[serde(default)]
+    mdc: Option<Snapshot>,
+}
+
+impl OwnedRecord {
+    pub(crate) fn new(record: &Record<'_>) -> Self {
+        OwnedRecord {
+            level: record.level(),
+            target: record.target().to_string(),
+            file: record.file().map(ToString::to_string),
+            line: record.line(),
+            message: record.message().to_string(),
+            safe_params: to_owned_params(record.safe_params()),
+            unsafe_params: to_owned_params(record.unsafe_params()),
+            tags: record
+                .tags()
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            error: record.error().map(OwnedError::from),
+            timestamp: record.timestamp(),
+            thread_name: record.thread_name().map(ToString::to_string),
+            thread_id: record.thread_id(),
+            mdc: record.mdc().cloned(),
+        }
+    }
+
+    /// Returns the record's verbosity level.
+    #[inline]
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns the record's target.
+    #[inline]
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns the file containing the code that created the record.
+    #[inline]
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Returns the line of the code that created the record.
+    #[inline]
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Returns the record's message.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the record's safe-loggable parameters.
+    #[inline]
+    pub fn safe_params(&self) -> &[(String, Any)] {
+        &self.safe_params
+    }
+
+    /// Returns the record's unsafe-loggable parameters.
+    #[inline]
+    pub fn unsafe_params(&self) -> &[(String, Any)] {
+        &self.unsafe_params
+    }
+
+    /// Returns the record's tags.
+    #[inline]
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// Returns the error associated with the record.
+    #[inline]
+    pub fn error(&self) -> Option<&OwnedError> {
+        self.error.as_ref()
+    }
+
+    /// Returns the time at which the record was created.
+    #[inline]
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.timestamp
+    }
+
+    /// Returns the name of the thread that created the record.
+    #[inline]
+    pub fn thread_name(&self) -> Option<&str> {
+        self.thread_name.as_deref()
+    }
+
+    /// Returns the ID of the thread that created the record.
+    #[inline]
+    pub fn thread_id(&self) -> Option<ThreadId> {
+        self.thread_id
+    }
+
+    /// Returns a snapshot of the MDC at the time the record was created.
+    #[inline]
+    pub fn mdc(&self) -> Option<&Snapshot> {
+        self.mdc.as_ref()
+    }
+
+    /// Calls a closure with a borrowed [`Record`] view of this record.
+    pub fn as_record<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Record<'_>) -> R,
+    {
+        let safe_params = to_borrowed_params(&self.safe_params);
+        let unsafe_params = to_borrowed_params(&self.unsafe_params);
+        let tags = self
+            .tags
+            .iter()
+            .map(|(k, v)| (&**k, &**v))
+            .collect::<Vec<_>>();
+
+        f(&Record::builder()
+            .level(self.level)
+            .target(&self.target)
+            .file(self.file.as_deref())
+            .line(self.line)
+            .message(&self.message)
+            .safe_params(&safe_params)
+            .unsafe_params(&unsafe_params)
+            .tags(&tags)
+            .error(self.error.as_ref().map(|e| e as _))
+            .timestamp(self.timestamp)
+            .thread_name(self.thread_name.as_deref())
+            .thread_id(self.thread_id)
+            .mdc(self.mdc.as_ref())
+            .build())
+    }
+}
+
+// a failing Serialize implementation shouldn't turn a log call into a panic, so those parameters are dropped
+fn to_owned_params(params: &[(&str, &dyn ErasedSerialize)]) -> Vec<(String, Any)> {
+    params
+        .iter()
+        .filter_map(|(key, value)| Some((key.to_string(), Any::new(value).ok()?)))
+        .collect()
+}
+
+fn to_borrowed_params(params: &[(String, Any)]) -> Vec<(&str, &dyn ErasedSerialize)> {
+    params
+        .iter()
+        .map(|(key, value)| (&**key, value as &dyn ErasedSerialize))
+        .collect()
+}
+
+/// An owned, flattened representation of a [`LogError`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnedError {
+    causes: Vec<String>,
+    cause_safe: bool,
+    #[serde(default)]
+    service_error: Option<SerializableError>,
+    #[serde(default)]
+    safe_params: BTreeMap<String, Any>,
+    #[serde(default)]
+    unsafe_params: BTreeMap<String, Any>,
+    #[serde(default)]
+    backtraces: Vec<String>,
+}
+
+impl<T> From<&T> for OwnedError
+where
+    T: ?Sized + LogError,
+{
+    fn from(error: &T) -> Self {
+        OwnedError {
+            causes: error.causes(),
+            cause_safe: error.cause_safe(),
+            service_error: error.service_error().cloned(),
+            safe_params: to_owned_error_params(error.safe_params()),
+            unsafe_params: to_owned_error_params(error.unsafe_params()),
+            backtraces: error.backtraces(),
+        }
+    }
+}
+
+fn to_owned_error_params(params: Vec<(&str, &Any)>) -> BTreeMap<String, Any> {
+    params
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+impl OwnedError {
+    /// Returns the messages of the error's cause chain, starting with the error's cause itself.
+    #[inline]
+    pub fn causes(&self) -> &[String] {
+        &self.causes
+    }
+
+    /// Returns whether or not the error's cause is considered safe.
+    #[inline]
+    pub fn cause_safe(&self) -> bool {
+        self.cause_safe
+    }
+
+    /// Returns the service error information, if the error was a service error.
+    #[inline]
+    pub fn service_error(&self) -> Option<&SerializableError> {
+        self.service_error.as_ref()
+    }
+
+    /// Returns the error's safe parameters.
+    #[inline]
+    pub fn safe_params(&self) -> &BTreeMap<String, Any> {
+        &self.safe_params
+    }
+
+    /// Returns the error's unsafe parameters.
+    #[inline]
+    pub fn unsafe_params(&self) -> &BTreeMap<String, Any> {
+        &self.unsafe_params
+    }
+
+    /// Returns the error's formatted backtraces, ordered from oldest to newest.
+    #[inline]
+    pub fn backtraces(&self) -> &[String] {
+        &self.backtraces
+    }
+}
+
+impl LogError for OwnedError {
+    fn causes(&self) -> Vec<String> {
+        self.causes.clone()
+    }
+
+    fn cause_safe(&self) -> bool {
+        self.cause_safe
+    }
+
+    fn service_error(&self) -> Option<&SerializableError> {
+        self.service_error.as_ref()
+    }
+
+    fn safe_params(&self) -> Vec<(&str, &Any)> {
+        self.safe_params.iter().map(|(k, v)| (&**k, v)).collect()
+    }
+
+    fn unsafe_params(&self) -> Vec<(&str, &Any)> {
+        self.unsafe_params.iter().map(|(k, v)| (&**k, v)).collect()
+    }
+
+    fn backtraces(&self) -> Vec<String> {
+        self.backtraces.clone()
+    }
+}
+
+mod params {
+    use serde::de::{MapAccess, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    pub fn serialize<S, V>(params: &[(String, V)], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+    {
+        serializer.collect_map(params.iter().map(|(k, v)| (k, v)))
+    }
+
+    pub fn deserialize<'de, D, V>(deserializer: D) -> Result<Vec<(String, V)>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+    {
+        struct ParamsVisitor<V>(PhantomData<V>);
+
+        impl<'de, V> Visitor<'de> for ParamsVisitor<V>
+        where
+            V: Deserialize<'de>,
+        {
+            type Value = Vec<(String, V)>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut params = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    params.push(entry);
+                }
+                Ok(params)
+            }
+        }
+
+        deserializer.deserialize_map(ParamsVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for logging panics.
+//!
+//! By default, panics are written to standard error in an unstructured format. This module provides a panic hook which
+//! logs them through the `witchcraft-log` logger as well. The hook chains to the previously registered one, so to stop
+//! panics from also being written to standard error, replace the default hook with [`std::panic::set_hook`] before
+//! installing this one.
+//!
+//! # Examples
+//!
+//! ```
+//! // suppress the default unstructured output
+//! std::panic::set_hook(Box::new(|_| {}));
+//! witchcraft_log::panic::install_hook();
+//! ```
+use crate::{mdc, Level, LogError, Metadata, Record};
+use conjure_object::Utc;
+use erased_serde::Serialize;
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::sync::Once;
+use std::thread;
+
+/// Installs a panic hook which logs panics at the "fatal" level.
+///
+/// The record's message is `panic` and its target is `witchcraft_log::panic`, regardless of where the panic occurred,
+/// so target-based filtering applies to all panics at once. The location of the panic is instead recorded as the
+/// record's file and line. The panic's payload is included as the `payload` parameter. The parameter is safe if the
+/// payload is a `&'static str`, and unsafe otherwise. The record's error has the payload as its unsafe cause and a
+/// backtrace of the panicking thread.
+///
+/// The logger is flushed after the panic is logged, and the previously registered panic hook is then called. Calling
+/// this function more than once has no effect.
+pub fn install_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            log_panic(info);
+            previous(info);
+        }));
+    });
+}
+
+fn log_panic(info: &PanicHookInfo<'_>) {
+    let target = module_path!();
+    if Level::Fatal > crate::max_level()
+        || !crate::logger().enabled(
+            &Metadata::builder()
+                .level(Level::Fatal)
+                .target(target)
+                .build(),
+        )
+    {
+        return;
+    }
+
+    let payload = info.payload();
+    let mut safe_params: &[(&'static str, &dyn Serialize)] = &[];
+    let mut unsafe_params: &[(&'static str, &dyn Serialize)] = &[];
+    let static_payload;
+    let string_payload;
+    let cause = if let Some(payload) = payload.downcast_ref::<&'static str>() {
+        static_payload = [("payload", payload as _)];
+        safe_params = &static_payload;
+        payload
+    } else if let Some(payload) = payload.downcast_ref::<String>() {
+        string_payload = [("payload", payload as _)];
+        unsafe_params = &string_payload;
+        payload
+    } else {
+        "Box<dyn Any>"
+    };
+
+    let error = PanicError {
+        cause,
+        backtrace: Backtrace::force_capture(),
+    };
+    let thread = thread::current();
+    let mdc = mdc::snapshot();
+
+    let logger = crate::logger();
+    logger.log(
+        &Record::builder()
+            .level(Level::Fatal)
+            .target(target)
+            .file(info.location().map(|l| l.file()))
+            .line(info.location().map(|l| l.line()))
+            .message("panic")
+            .safe_params(safe_params)
+            .unsafe_params(unsafe_params)
+            .error(Some(&error))
+            .timestamp(Some(Utc::now()))
+            .thread_name(thread.name())
+            .thread_id(Some(thread.id()))
+            .mdc(Some(&mdc))
+            .build(),
+    );
+    logger.flush();
+}
+
+struct PanicError<'a> {
+    cause: &'a str,
+    backtrace: Backtrace,
+}
+
+impl LogError for PanicError<'_> {
+    fn causes(&self) -> Vec<String> {
+        vec![self.cause.to_string()]
+    }
+
+    fn backtraces(&self) -> Vec<String> {
+        vec![self.backtrace.to_string()]
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use erased_serde::Serialize;
+
+/// A type which can be included in a record as a set of safe and unsafe parameters.
+///
+/// Values are included in a record via the `params` section of the logging macros. With the `derive` Cargo feature
+/// enabled, this trait can be derived for structs with named fields. Fields annotated `#[safe]` are included as safe
+/// parameters, fields annotated `#[skip]` are excluded, and all other fields are included as unsafe parameters. A
+/// field can also be explicitly annotated as unsafe, though since `unsafe` is a keyword the attribute has to be written
+/// as `#[r#unsafe]`. Parameters are named after their fields.
+///
+#[cfg_attr(
+    feature = "derive",
+    doc = r#"
+# Examples
+
+```
+use witchcraft_log::LogParams;
+
+#[derive(LogParams)]
+struct ShaveRequest {
+    #[safe]
+    attempt: u32,
+    #[r#unsafe]
+    yak: String,
+    #[skip]
+    razor: (),
+}
+
+# let request = ShaveRequest { attempt: 1, yak: String::new(), razor: () };
+witchcraft_log::info!("shaving yak", params: request);
+```
+"#
+)]
+pub trait LogParams {
+    /// Adds the value's parameters to the collection.
+    fn log_params<'a>(&'a self, params: &mut Params<'a>);
+}
+
+impl<T> LogParams for &T
+where
+    T: ?Sized + LogParams,
+{
+    fn log_params<'a>(&'a self, params: &mut Params<'a>) {
+        (**self).log_params(params)
+    }
+}
+
+/// A collection of safe and unsafe parameters.
+#[derive(Default)]
+pub struct Params<'a> {
+    safe: Vec<(&'static str, &'a dyn Serialize)>,
+    unsafe_: Vec<(&'static str, &'a dyn Serialize)>,
+}
+
+impl<'a> Params<'a> {
+    /// Creates a new, empty collection.
+    #[inline]
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    /// Adds a safe parameter.
+    #[inline]
+    pub fn insert_safe(&mut self, key: &'static str, value: &'a dyn Serialize) {
+        self.safe.push((key, value));
+    }
+
+    /// Adds an unsafe parameter.
+    #[inline]
+    pub fn insert_unsafe(&mut self, key: &'static str, value: &'a dyn Serialize) {
+        self.unsafe_.push((key, value));
+    }
+
+    /// Returns the safe parameters.
+    #[inline]
+    pub fn safe(&self) -> &[(&'static str, &'a dyn Serialize)] {
+        &self.safe
+    }
+
+    /// Returns the unsafe parameters.
+    #[inline]
+    pub fn unsafe_(&self) -> &[(&'static str, &'a dyn Serialize)] {
+        &self.unsafe_
+    }
+}
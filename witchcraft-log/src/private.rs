@@ -11,9 +11,18 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use crate::{Level, Metadata, Record};
-use conjure_error::Error;
-use erased_serde::Serialize;
+use crate::logger::{LEVEL_BITS, LEVEL_MASK};
+use crate::{mdc, Level, LogError, LogParams, Metadata, Params, Record, SafeArg};
+use conjure_object::Utc;
+pub use erased_serde::Serialize;
+pub use serde;
+use std::cell::{Cell, OnceCell};
+use std::error;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub fn log(
     level: Level,
@@ -21,8 +30,36 @@ pub fn log(
     &(target, file, line, message): &(&str, &str, u32, &'static str),
     safe_params: &[(&'static str, &dyn Serialize)],
     unsafe_params: &[(&'static str, &dyn Serialize)],
-    error: Option<&Error>,
+    params: &[&dyn LogParams],
+    tags: &[(&'static str, &str)],
+    error: Option<&dyn LogError>,
 ) {
+    if !params.is_empty() {
+        let mut all_params = Params::new();
+        for (key, value) in safe_params {
+            all_params.insert_safe(key, *value);
+        }
+        for (key, value) in unsafe_params {
+            all_params.insert_unsafe(key, *value);
+        }
+        for params in params {
+            params.log_params(&mut all_params);
+        }
+
+        log(
+            level,
+            &(target, file, line, message),
+            all_params.safe(),
+            all_params.unsafe_(),
+            &[],
+            tags,
+            error,
+        );
+        return;
+    }
+
+    let thread = thread::current();
+    let mdc = mdc::snapshot();
     crate::logger().log(
         &Record::builder()
             .level(level)
@@ -32,12 +69,19 @@ pub fn log(
             .message(message)
             .safe_params(safe_params)
             .unsafe_params(unsafe_params)
+            .tags(tags)
             .error(error)
+            .timestamp(Some(Utc::now()))
+            .thread_name(thread.name())
+            .thread_id(Some(thread.id()))
+            .mdc(Some(&mdc))
             .build(),
     )
 }
 
 pub fn log_minimal(level: Level, &(target, file, line, message): &(&str, &str, u32, &'static str)) {
+    let thread = thread::current();
+    let mdc = mdc::snapshot();
     crate::logger().log(
         &Record::builder()
             .level(level)
@@ -45,10 +89,415 @@ pub fn log_minimal(level: Level, &(target, file, line, message): &(&str, &str, u
             .file(Some(file))
             .line(Some(line))
             .message(message)
+            .timestamp(Some(Utc::now()))
+            .thread_name(thread.name())
+            .thread_id(Some(thread.id()))
+            .mdc(Some(&mdc))
             .build(),
     )
 }
 
+/// Converts the value in the `error` section of the logging macros into a `LogError`.
+///
+/// Types implementing `LogError` are used directly. Any other type implementing `std::error::Error` falls back to the
+/// first deref target's method, and any other type which derefs to a `LogError` (e.g. `Box<dyn Error + Send + Sync>`
+/// or `anyhow::Error`) falls back to the second deref target's method.
+pub struct ErrorWrap<'a, T: ?Sized>(StdErrorWrap<'a, T>);
+
+impl<'a, T> ErrorWrap<'a, T>
+where
+    T: ?Sized,
+{
+    #[inline]
+    pub fn new(error: &'a T) -> Self {
+        ErrorWrap(StdErrorWrap(DerefErrorWrap(error)))
+    }
+}
+
+impl<'a, T> ErrorWrap<'a, T>
+where
+    T: ?Sized + LogError,
+{
+    #[inline]
+    pub fn log_error(&self) -> &'a T {
+        self.0 .0 .0
+    }
+}
+
+impl<'a, T> Deref for ErrorWrap<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = StdErrorWrap<'a, T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct StdErrorWrap<'a, T: ?Sized>(DerefErrorWrap<'a, T>);
+
+impl<'a, T> StdErrorWrap<'a, T>
+where
+    T: error::Error + 'static,
+{
+    #[inline]
+    pub fn log_error(&self) -> &'a (dyn error::Error + 'static) {
+        self.0 .0
+    }
+}
+
+impl<'a, T> Deref for StdErrorWrap<'a, T>
+where
+    T: ?Sized,
+{
+    type Target = DerefErrorWrap<'a, T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct DerefErrorWrap<'a, T: ?Sized>(&'a T);
+
+impl<'a, T> DerefErrorWrap<'a, T>
+where
+    T: ?Sized + Deref,
+    T::Target: LogError,
+{
+    #[inline]
+    pub fn log_error(&self) -> &'a T::Target {
+        self.0
+    }
+}
+
+/// A parameter value computed the first time it is serialized.
+pub struct Lazy<F, T> {
+    f: Cell<Option<F>>,
+    value: OnceCell<T>,
+}
+
+impl<F, T> Lazy<F, T>
+where
+    F: FnOnce() -> T,
+{
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Lazy {
+            f: Cell::new(Some(f)),
+            value: OnceCell::new(),
+        }
+    }
+}
+
+impl<F, T> serde::Serialize for Lazy<F, T>
+where
+    F: FnOnce() -> T,
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.value
+            .get_or_init(|| (self.f.take().expect("lazy value reentrantly serialized"))())
+            .serialize(serializer)
+    }
+}
+
+impl<F, T> SafeArg for Lazy<F, T> where T: SafeArg {}
+
+/// The bound on the values of safe parameters.
+#[cfg(feature = "strict_safety")]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not known to be safe to log",
+    label = "not known to be safe",
+    note = "wrap the value in `witchcraft_log::Safe` if it is safe, or move it to the `unsafe` section"
+)]
+pub trait SafeParam: SafeArg + serde::Serialize {}
+
+#[cfg(feature = "strict_safety")]
+impl<T> SafeParam for T where T: ?Sized + SafeArg + serde::Serialize {}
+
+/// The bound on the values of safe parameters.
+#[cfg(not(feature = "strict_safety"))]
+pub trait SafeParam: serde::Serialize {}
+
+#[cfg(not(feature = "strict_safety"))]
+impl<T> SafeParam for T where T: ?Sized + serde::Serialize {}
+
+/// The bound on the values of safe MDC keys.
+#[cfg(feature = "strict_safety")]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not known to be safe to log",
+    label = "not known to be safe",
+    note = "use `witchcraft_log::mdc::Key::assume_safe` if all of its values are safe, or make the key unsafe"
+)]
+pub trait SafeKeyValue: SafeArg {}
+
+#[cfg(feature = "strict_safety")]
+impl<T> SafeKeyValue for T where T: ?Sized + SafeArg {}
+
+/// The bound on the values of safe MDC keys.
+#[cfg(not(feature = "strict_safety"))]
+pub trait SafeKeyValue {}
+
+#[cfg(not(feature = "strict_safety"))]
+impl<T> SafeKeyValue for T where T: ?Sized {}
+
+#[inline(always)]
+pub fn safe_param<T>(value: &T) -> &dyn Serialize
+where
+    T: SafeParam,
+{
+    value
+}
+
+#[inline(always)]
+pub fn unsafe_param<T>(value: &T) -> &dyn Serialize
+where
+    T: serde::Serialize,
+{
+    value
+}
+
+#[inline]
+pub fn skip_suppressed<'a, 'b>(
+    safe_params: &'a [(&'static str, &'b dyn Serialize)],
+    suppressed: u64,
+) -> &'a [(&'static str, &'b dyn Serialize)] {
+    if suppressed == 0 {
+        &safe_params[1..]
+    } else {
+        safe_params
+    }
+}
+
 pub fn enabled(level: Level, target: &str) -> bool {
     crate::logger().enabled(&Metadata::builder().level(level).target(target).build())
 }
+
+// Each level has 2 bits of interest state, stored below the generation.
+const INTEREST_BITS: u32 = 2 * 6;
+const GENERATION_MASK: usize = usize::MAX >> INTEREST_BITS;
+const INTEREST_ENABLED: usize = 0b01;
+const INTEREST_DISABLED: usize = 0b10;
+
+/// Per-callsite cache of the logger's `enabled` result for each level.
+pub struct Callsite {
+    state: AtomicUsize,
+}
+
+impl Callsite {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Callsite {
+            // an all-zero state would be fresh for the initial generation
+            state: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// Determines if a record at the specified level would be logged at this callsite.
+    #[inline]
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        self.enabled_at(crate::logger::state(), level, || enabled(level, target))
+    }
+
+    // split out from `enabled` so tests don't depend on the global logger
+    #[inline]
+    fn enabled_at<F>(&self, global: usize, level: Level, query: F) -> bool
+    where
+        F: FnOnce() -> bool,
+    {
+        if level as usize > global & LEVEL_MASK {
+            return false;
+        }
+
+        let generation = (global >> LEVEL_BITS) & GENERATION_MASK;
+        let shift = 2 * (level as usize - 1);
+        let state = self.state.load(Ordering::Relaxed);
+        if state >> INTEREST_BITS == generation {
+            match (state >> shift) & 0b11 {
+                INTEREST_ENABLED => return true,
+                INTEREST_DISABLED => return false,
+                _ => {}
+            }
+        }
+
+        self.register(generation, shift, query)
+    }
+
+    #[cold]
+    fn register<F>(&self, generation: usize, shift: usize, query: F) -> bool
+    where
+        F: FnOnce() -> bool,
+    {
+        let enabled = query();
+        let interest = if enabled {
+            INTEREST_ENABLED
+        } else {
+            INTEREST_DISABLED
+        } << shift;
+
+        let _ = self
+            .state
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |state| {
+                if state >> INTEREST_BITS == generation {
+                    Some(state | interest)
+                } else {
+                    Some((generation << INTEREST_BITS) | interest)
+                }
+            });
+
+        enabled
+    }
+}
+
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+
+/// Per-callsite state for rate limited log macros.
+pub struct RateLimiter {
+    // nanoseconds since EPOCH at which the next record may be logged
+    next: AtomicU64,
+    suppressed: AtomicU64,
+}
+
+impl RateLimiter {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        RateLimiter {
+            next: AtomicU64::new(0),
+            suppressed: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of suppressed records if a record should be logged.
+    pub fn check(&self, period: Duration) -> Option<u64> {
+        let now = EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as u64;
+        self.check_at(now, period)
+    }
+
+    // split out from `check` so tests don't depend on the wall clock
+    fn check_at(&self, now: u64, period: Duration) -> Option<u64> {
+        let next = self.next.load(Ordering::Relaxed);
+        let new_next = now.saturating_add(period.as_nanos() as u64);
+        if now < next
+            || self
+                .next
+                .compare_exchange(next, new_next, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        Some(self.suppressed.swap(0, Ordering::Relaxed))
+    }
+}
+
+/// Per-callsite state for log-once macros.
+pub struct Once(AtomicBool);
+
+impl Once {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Once(AtomicBool::new(false))
+    }
+
+    /// Returns `true` the first time it is called.
+    pub fn check(&self) -> bool {
+        !self.0.load(Ordering::Relaxed) && !self.0.swap(true, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::logger::LEVEL_BITS;
+    use crate::private::{Callsite, RateLimiter};
+    use crate::{Level, LevelFilter};
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    fn global(generation: usize, level: LevelFilter) -> usize {
+        (generation << LEVEL_BITS) | level as usize
+    }
+
+    #[test]
+    fn rate_limiter() {
+        let limiter = RateLimiter::new();
+        let period = Duration::from_nanos(100);
+
+        assert_eq!(limiter.check_at(1_000, period), Some(0));
+        assert_eq!(limiter.check_at(1_050, period), None);
+        assert_eq!(limiter.check_at(1_099, period), None);
+        assert_eq!(limiter.check_at(1_100, period), Some(2));
+        assert_eq!(limiter.check_at(1_150, period), None);
+        assert_eq!(limiter.check_at(5_000, period), Some(1));
+        assert_eq!(limiter.check_at(5_100, period), Some(0));
+    }
+
+    #[test]
+    fn callsite_caches_interest() {
+        let callsite = Callsite::new();
+        let queries = Cell::new(0);
+        let query = |enabled| {
+            queries.set(queries.get() + 1);
+            enabled
+        };
+
+        assert!(callsite.enabled_at(global(0, LevelFilter::Trace), Level::Info, || query(true)));
+        assert!(callsite.enabled_at(global(0, LevelFilter::Trace), Level::Info, || query(false)));
+        assert_eq!(queries.get(), 1);
+    }
+
+    #[test]
+    fn callsite_stale_generation() {
+        let callsite = Callsite::new();
+        let queries = Cell::new(0);
+        let query = |enabled| {
+            queries.set(queries.get() + 1);
+            enabled
+        };
+
+        assert!(callsite.enabled_at(global(0, LevelFilter::Trace), Level::Info, || query(true)));
+        assert!(!callsite.enabled_at(global(1, LevelFilter::Trace), Level::Info, || query(false)));
+        assert!(!callsite.enabled_at(global(1, LevelFilter::Trace), Level::Info, || query(true)));
+        assert_eq!(queries.get(), 2);
+    }
+
+    #[test]
+    fn callsite_levels_independent() {
+        let callsite = Callsite::new();
+        let queries = Cell::new(0);
+        let query = |enabled| {
+            queries.set(queries.get() + 1);
+            enabled
+        };
+
+        assert!(callsite.enabled_at(global(0, LevelFilter::Trace), Level::Warn, || query(true)));
+        assert!(!callsite.enabled_at(global(0, LevelFilter::Trace), Level::Debug, || query(false)));
+        assert!(callsite.enabled_at(global(0, LevelFilter::Trace), Level::Warn, || query(false)));
+        assert!(!callsite.enabled_at(global(0, LevelFilter::Trace), Level::Debug, || query(true)));
+        assert_eq!(queries.get(), 2);
+
+        // a level which has not been queried yet isn't affected by the others
+        assert!(callsite.enabled_at(global(0, LevelFilter::Trace), Level::Info, || query(true)));
+        assert_eq!(queries.get(), 3);
+    }
+
+    #[test]
+    fn callsite_max_level() {
+        let callsite = Callsite::new();
+        let queries = Cell::new(0);
+        let query = |enabled| {
+            queries.set(queries.get() + 1);
+            enabled
+        };
+
+        assert!(callsite.enabled_at(global(0, LevelFilter::Trace), Level::Debug, || query(true)));
+        assert!(!callsite.enabled_at(global(1, LevelFilter::Info), Level::Debug, || query(true)));
+        assert_eq!(queries.get(), 1);
+    }
+}
@@ -11,9 +11,11 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-use crate::Level;
-use conjure_error::Error;
+use crate::mdc::Snapshot;
+use crate::{Level, LogError, OwnedRecord};
+use conjure_object::{DateTime, Utc};
 use erased_serde::Serialize;
+use std::thread::ThreadId;
 
 /// Metadata of a log record.
 #[derive(Clone)]
@@ -92,10 +94,15 @@ pub struct Record<'a> {
     metadata: Metadata<'a>,
     file: Option<&'a str>,
     line: Option<u32>,
-    message: &'static str,
-    safe_params: &'a [(&'static str, &'a dyn Serialize)],
-    unsafe_params: &'a [(&'static str, &'a dyn Serialize)],
-    error: Option<&'a Error>,
+    message: &'a str,
+    safe_params: &'a [(&'a str, &'a dyn Serialize)],
+    unsafe_params: &'a [(&'a str, &'a dyn Serialize)],
+    tags: &'a [(&'a str, &'a str)],
+    error: Option<&'a dyn LogError>,
+    timestamp: Option<DateTime<Utc>>,
+    thread_name: Option<&'a str>,
+    thread_id: Option<ThreadId>,
+    mdc: Option<&'a Snapshot>,
 }
 
 impl<'a> Record<'a> {
@@ -137,27 +144,75 @@ impl<'a> Record<'a> {
 
     /// Returns the record's message.
     #[inline]
-    pub fn message(&self) -> &'static str {
+    pub fn message(&self) -> &'a str {
         self.message
     }
 
     /// Returns the record's safe-loggable parameters.
     #[inline]
-    pub fn safe_params(&self) -> &'a [(&'static str, &dyn Serialize)] {
+    pub fn safe_params(&self) -> &'a [(&'a str, &'a dyn Serialize)] {
         self.safe_params
     }
 
     /// Returns the record's unsafe-loggable parameters.
     #[inline]
-    pub fn unsafe_params(&self) -> &'a [(&'static str, &dyn Serialize)] {
+    pub fn unsafe_params(&self) -> &'a [(&'a str, &'a dyn Serialize)] {
         self.unsafe_params
     }
 
+    /// Returns the record's tags.
+    #[inline]
+    pub fn tags(&self) -> &'a [(&'a str, &'a str)] {
+        self.tags
+    }
+
     /// Returns the error associated with the record.
     #[inline]
-    pub fn error(&self) -> Option<&'a Error> {
+    pub fn error(&self) -> Option<&'a dyn LogError> {
         self.error
     }
+
+    /// Returns the time at which the record was created.
+    ///
+    /// Records created by the logging macros will always have a timestamp.
+    #[inline]
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.timestamp
+    }
+
+    /// Returns the name of the thread that created the record.
+    #[inline]
+    pub fn thread_name(&self) -> Option<&'a str> {
+        self.thread_name
+    }
+
+    /// Returns the ID of the thread that created the record.
+    #[inline]
+    pub fn thread_id(&self) -> Option<ThreadId> {
+        self.thread_id
+    }
+
+    /// Returns a snapshot of the MDC at the time the record was created.
+    ///
+    /// Records created by the logging macros will always have an MDC snapshot. Loggers should fall back to the current
+    /// state of the MDC if it is not present.
+    #[inline]
+    pub fn mdc(&self) -> Option<&'a Snapshot> {
+        self.mdc
+    }
+
+    /// Returns a `RecordBuilder` initialized to the values of this record.
+    #[inline]
+    pub fn to_builder(&self) -> RecordBuilder<'a> {
+        RecordBuilder(self.clone())
+    }
+
+    /// Converts the record into an [`OwnedRecord`] which can outlive it.
+    ///
+    /// Parameters which cannot be serialized into a [`conjure_object::Any`] are omitted from the owned record.
+    pub fn to_owned(&self) -> OwnedRecord {
+        OwnedRecord::new(self)
+    }
 }
 
 /// A builder for `Record` values.
@@ -180,7 +235,12 @@ impl<'a> RecordBuilder<'a> {
             message: "",
             safe_params: &[],
             unsafe_params: &[],
+            tags: &[],
             error: None,
+            timestamp: None,
+            thread_name: None,
+            thread_id: None,
+            mdc: None,
         })
     }
 
@@ -224,7 +284,7 @@ impl<'a> RecordBuilder<'a> {
     ///
     /// Defaults to `""`.
     #[inline]
-    pub fn message(&mut self, message: &'static str) -> &mut RecordBuilder<'a> {
+    pub fn message(&mut self, message: &'a str) -> &mut RecordBuilder<'a> {
         self.0.message = message;
         self
     }
@@ -235,7 +295,7 @@ impl<'a> RecordBuilder<'a> {
     #[inline]
     pub fn safe_params(
         &mut self,
-        safe_params: &'a [(&'static str, &dyn Serialize)],
+        safe_params: &'a [(&'a str, &'a dyn Serialize)],
     ) -> &mut RecordBuilder<'a> {
         self.0.safe_params = safe_params;
         self
@@ -245,21 +305,66 @@ impl<'a> RecordBuilder<'a> {
     #[inline]
     pub fn unsafe_params(
         &mut self,
-        unsafe_params: &'a [(&'static str, &dyn Serialize)],
+        unsafe_params: &'a [(&'a str, &'a dyn Serialize)],
     ) -> &mut RecordBuilder<'a> {
         self.0.unsafe_params = unsafe_params;
         self
     }
 
+    /// Sets the record's tags.
+    ///
+    /// Defaults to `[]`.
+    #[inline]
+    pub fn tags(&mut self, tags: &'a [(&'a str, &'a str)]) -> &mut RecordBuilder<'a> {
+        self.0.tags = tags;
+        self
+    }
+
     /// Sets the record's error.
     ///
     /// Defaults to `None`.
     #[inline]
-    pub fn error(&mut self, error: Option<&'a Error>) -> &mut RecordBuilder<'a> {
+    pub fn error(&mut self, error: Option<&'a dyn LogError>) -> &mut RecordBuilder<'a> {
         self.0.error = error;
         self
     }
 
+    /// Sets the record's timestamp.
+    ///
+    /// Defaults to `None`.
+    #[inline]
+    pub fn timestamp(&mut self, timestamp: Option<DateTime<Utc>>) -> &mut RecordBuilder<'a> {
+        self.0.timestamp = timestamp;
+        self
+    }
+
+    /// Sets the record's thread name.
+    ///
+    /// Defaults to `None`.
+    #[inline]
+    pub fn thread_name(&mut self, thread_name: Option<&'a str>) -> &mut RecordBuilder<'a> {
+        self.0.thread_name = thread_name;
+        self
+    }
+
+    /// Sets the record's thread ID.
+    ///
+    /// Defaults to `None`.
+    #[inline]
+    pub fn thread_id(&mut self, thread_id: Option<ThreadId>) -> &mut RecordBuilder<'a> {
+        self.0.thread_id = thread_id;
+        self
+    }
+
+    /// Sets the record's MDC snapshot.
+    ///
+    /// Defaults to `None`.
+    #[inline]
+    pub fn mdc(&mut self, mdc: Option<&'a Snapshot>) -> &mut RecordBuilder<'a> {
+        self.0.mdc = mdc;
+        self
+    }
+
     /// Creates a `Record`.
     #[inline]
     pub fn build(&self) -> Record<'a> {
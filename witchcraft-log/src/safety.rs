@@ -0,0 +1,214 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{Level, LevelFilter};
+use serde::{Serialize, Serializer};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::time::Duration;
+
+/// A marker trait for types whose values are always safe to log.
+///
+/// With the `strict_safety` Cargo feature enabled, values in the `safe` section of the logging macros and fields of a
+/// derived [`LogParams`](crate::LogParams) annotated `#[safe]` must implement this trait. It is implemented for
+/// numbers, booleans, durations, and collections of safe values. Other types, like enums of a fixed set of states,
+/// can opt in by implementing it. Individual values can be marked as safe by wrapping them in [`Safe`].
+///
+/// The feature is not additive, since it applies to every crate in the build once any crate enables it. Libraries
+/// should wrap their safe parameters so that they compile either way.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::SafeArg;
+///
+/// #[derive(serde::Serialize)]
+/// enum Stage {
+///     Startup,
+///     Running,
+/// }
+///
+/// impl SafeArg for Stage {}
+///
+/// witchcraft_log::info!("stage changed", safe: { stage: Stage::Running });
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not known to be safe to log",
+    label = "not known to be safe",
+    note = "wrap the value in `witchcraft_log::Safe` if it is safe, or move it to the `unsafe` section"
+)]
+pub trait SafeArg {}
+
+macro_rules! impl_safe_arg {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl SafeArg for $t {}
+        )*
+    };
+}
+
+impl_safe_arg!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    bool,
+    (),
+    Duration,
+    Level,
+    LevelFilter,
+);
+
+impl<T> SafeArg for &T where T: ?Sized + SafeArg {}
+
+impl<T> SafeArg for Option<T> where T: SafeArg {}
+
+impl<T> SafeArg for [T] where T: SafeArg {}
+
+impl<T, const N: usize> SafeArg for [T; N] where T: SafeArg {}
+
+impl<T> SafeArg for Vec<T> where T: SafeArg {}
+
+impl<T> SafeArg for Safe<T> {}
+
+/// A wrapper which marks a value as safe to log.
+///
+/// This overrides the checks made with the `strict_safety` Cargo feature enabled, so it should only be used for values
+/// known to be safe, like strings from a fixed set of constants.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::Safe;
+///
+/// let endpoint = "getYak";
+/// witchcraft_log::info!("handled request", safe: { endpoint: Safe(endpoint) });
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Safe<T>(pub T);
+
+impl<T> Serialize for Safe<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+/// A wrapper which marks a value as unsafe to log.
+///
+/// It never implements [`SafeArg`], so with the `strict_safety` Cargo feature enabled, it prevents a value of an
+/// otherwise safe type from being logged as a safe parameter.
+///
+/// # Examples
+///
+/// ```
+/// use witchcraft_log::Unsafe;
+///
+/// # let balance = 0;
+/// witchcraft_log::info!("loaded account", unsafe: { balance: Unsafe(balance) });
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Unsafe<T>(pub T);
+
+impl<T> Serialize for Unsafe<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+/// Values which must be rejected with the `strict_safety` Cargo feature enabled.
+///
+/// ```compile_fail,E0277
+/// let user = String::from("bob");
+/// witchcraft_log::info!("message", safe: { user: user });
+/// ```
+///
+/// ```compile_fail,E0277
+/// witchcraft_log::info!("message", safe: { user: "bob" });
+/// ```
+///
+/// ```compile_fail,E0277
+/// # let balance = 0;
+/// witchcraft_log::info!("message", safe: { balance: witchcraft_log::Unsafe(balance) });
+/// ```
+///
+/// ```compile_fail,E0277
+/// witchcraft_log::mdc::with!(safe: { user: "bob" }, {});
+/// ```
+///
+/// ```compile_fail,E0277
+/// use witchcraft_log::mdc::Key;
+///
+/// const USER: Key<String> = Key::safe("user");
+/// ```
+#[cfg_attr(
+    feature = "derive",
+    doc = r#"
+```compile_fail,E0277
+#[derive(witchcraft_log::LogParams)]
+struct Request {
+    #[safe]
+    user: String,
+}
+```
+"#
+)]
+#[cfg(all(doctest, feature = "strict_safety"))]
+pub struct StrictSafetyViolations;
+
+#[cfg(test)]
+mod test {
+    use crate::{Safe, Unsafe};
+    use serde_test::{assert_ser_tokens, Token};
+
+    #[test]
+    fn transparent_serialization() {
+        assert_ser_tokens(&Safe(15), &[Token::I32(15)]);
+        assert_ser_tokens(&Unsafe("foobar"), &[Token::Str("foobar")]);
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for forwarding records from the `slog` crate to `witchcraft-log`.
+//!
+//! This module is only available with the `slog` Cargo feature enabled. It provides a [`WitchcraftDrain`] which
+//! converts `slog` records into `witchcraft-log` records and forwards them to the global logger.
+//!
+//! The key-value pairs of both the record and its logger are included as unsafe parameters by default, with the
+//! record's pairs taking precedence. Keys can be registered as safe with [`Builder::safe_key`]. As with the `log`
+//! bridge, static messages are used as the Witchcraft message, but formatted messages are conservatively included as
+//! the unsafe `message` parameter.
+//!
+//! # Examples
+//!
+//! ```
+//! use slog::o;
+//! use witchcraft_log::slog::WitchcraftDrain;
+//!
+//! let drain = WitchcraftDrain::builder().safe_key("attempt").build();
+//! let logger = slog::Logger::root(drain, o!("service" => "yak-shaver"));
+//!
+//! slog::info!(logger, "shaving yak"; "attempt" => 3, "yak" => "bob");
+//! ```
+use crate::{mdc, Level, Metadata, Record};
+use conjure_object::{Any, Utc};
+use erased_serde::Serialize;
+use slog::{Drain, Key, Never, OwnedKVList, SerdeValue, KV};
+use std::collections::HashSet;
+use std::error::Error;
+use std::{fmt, thread};
+
+fn cvt_level(level: slog::Level) -> Level {
+    match level {
+        slog::Level::Critical => Level::Fatal,
+        slog::Level::Error => Level::Error,
+        slog::Level::Warning => Level::Warn,
+        slog::Level::Info => Level::Info,
+        slog::Level::Debug => Level::Debug,
+        slog::Level::Trace => Level::Trace,
+    }
+}
+
+/// A `slog::Drain` that forwards records to the `witchcraft-log` logger.
+///
+/// The "critical" level is mapped to the "fatal" level, and the record's module is used as its target.
+pub struct WitchcraftDrain {
+    safe_keys: HashSet<String>,
+}
+
+impl Default for WitchcraftDrain {
+    fn default() -> Self {
+        WitchcraftDrain::new()
+    }
+}
+
+impl WitchcraftDrain {
+    /// Creates a new drain with the default configuration.
+    ///
+    /// All key-value pairs are treated as unsafe parameters.
+    pub fn new() -> Self {
+        WitchcraftDrain::builder().build()
+    }
+
+    /// Returns a builder used to configure a drain.
+    pub fn builder() -> Builder {
+        Builder {
+            safe_keys: HashSet::new(),
+        }
+    }
+}
+
+impl Drain for WitchcraftDrain {
+    type Ok = ();
+
+    type Err = Never;
+
+    fn log(&self, record: &slog::Record<'_>, values: &OwnedKVList) -> Result<(), Never> {
+        let level = cvt_level(record.level());
+        let target = record.module();
+        if level > crate::max_level()
+            || !crate::logger().enabled(&Metadata::builder().level(level).target(target).build())
+        {
+            return Ok(());
+        }
+
+        let mut params = Params {
+            drain: self,
+            safe: vec![],
+            unsafe_: vec![],
+        };
+        let _ = record.kv().serialize(record, &mut params);
+        let _ = values.serialize(record, &mut params);
+
+        let safe_params = params.safe_params();
+        let mut unsafe_params = params.unsafe_params();
+
+        let thread = thread::current();
+        let mdc = mdc::snapshot();
+
+        let mut builder = Record::builder();
+        builder
+            .level(level)
+            .target(target)
+            .file(Some(record.file()))
+            .line(Some(record.line()))
+            .timestamp(Some(Utc::now()))
+            .thread_name(thread.name())
+            .thread_id(Some(thread.id()))
+            .mdc(Some(&mdc));
+
+        // If the log message is static, it is safe to log as the WC message. Otherwise, we have to conservatively
+        // assume it contains unsafe data.
+        let args = record.msg();
+        match args.as_str() {
+            Some(message) => {
+                builder.message(message);
+            }
+            None => unsafe_params.insert(0, ("message", args)),
+        }
+
+        crate::logger().log(
+            &builder
+                .safe_params(&safe_params)
+                .unsafe_params(&unsafe_params)
+                .build(),
+        );
+
+        Ok(())
+    }
+
+    fn is_enabled(&self, level: slog::Level) -> bool {
+        cvt_level(level) <= crate::max_level()
+    }
+}
+
+/// A builder for [`WitchcraftDrain`].
+pub struct Builder {
+    safe_keys: HashSet<String>,
+}
+
+impl Builder {
+    /// Registers a key whose values will be treated as safe parameters.
+    pub fn safe_key(mut self, key: &str) -> Self {
+        self.safe_keys.insert(key.to_string());
+        self
+    }
+
+    /// Creates the drain.
+    pub fn build(self) -> WitchcraftDrain {
+        WitchcraftDrain {
+            safe_keys: self.safe_keys,
+        }
+    }
+}
+
+struct Params<'a> {
+    drain: &'a WitchcraftDrain,
+    safe: Vec<(&'static str, Any)>,
+    unsafe_: Vec<(&'static str, Any)>,
+}
+
+impl Params<'_> {
+    fn safe_params(&self) -> Vec<(&'static str, &dyn Serialize)> {
+        self.safe.iter().map(|(k, v)| (*k, v as _)).collect()
+    }
+
+    fn unsafe_params(&self) -> Vec<(&'static str, &dyn Serialize)> {
+        self.unsafe_.iter().map(|(k, v)| (*k, v as _)).collect()
+    }
+
+    fn emit<T>(&mut self, key: Key, value: T) -> slog::Result
+    where
+        T: serde::Serialize,
+    {
+        let params = if self.drain.safe_keys.contains(key) {
+            &mut self.safe
+        } else {
+            &mut self.unsafe_
+        };
+
+        // the record's pairs are serialized first and take precedence over the logger's
+        if !params.iter().any(|(k, _)| *k == key) {
+            if let Ok(value) = Any::new(value) {
+                params.push((key, value));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! emit {
+    ($($method:ident: $t:ty,)*) => {
+        $(
+            fn $method(&mut self, key: Key, value: $t) -> slog::Result {
+                self.emit(key, value)
+            }
+        )*
+    };
+}
+
+impl slog::Serializer for Params<'_> {
+    emit! {
+        emit_usize: usize,
+        emit_isize: isize,
+        emit_bool: bool,
+        emit_char: char,
+        emit_u8: u8,
+        emit_i8: i8,
+        emit_u16: u16,
+        emit_i16: i16,
+        emit_u32: u32,
+        emit_i32: i32,
+        emit_f32: f32,
+        emit_u64: u64,
+        emit_i64: i64,
+        emit_f64: f64,
+        emit_u128: u128,
+        emit_i128: i128,
+        emit_str: &str,
+    }
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        self.emit(key, ())
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        self.emit(key, None::<()>)
+    }
+
+    fn emit_arguments(&mut self, key: Key, value: &fmt::Arguments<'_>) -> slog::Result {
+        self.emit(key, value)
+    }
+
+    fn emit_serde(&mut self, key: Key, value: &dyn SerdeValue) -> slog::Result {
+        self.emit(key, value.as_serde())
+    }
+
+    fn emit_error(&mut self, key: Key, error: &(dyn Error + 'static)) -> slog::Result {
+        let mut causes = vec![];
+        let mut cause = Some(error);
+        while let Some(e) = cause {
+            causes.push(e.to_string());
+            cause = e.source();
+        }
+        self.emit(key, causes.join(": "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::slog::WitchcraftDrain;
+    use crate::testing;
+    use crate::Level;
+    use slog::o;
+    use std::hint::black_box;
+
+    #[test]
+    fn drain() {
+        let drain = WitchcraftDrain::builder()
+            .safe_key("attempt")
+            .safe_key("service")
+            .build();
+        let logger = slog::Logger::root(drain, o!("service" => "yak-shaver", "yak" => "alice"));
+
+        let logs = testing::capture(|| {
+            slog::crit!(logger, "shaving yak"; "attempt" => 3, "yak" => "bob");
+            slog::debug!(logger, "shaved {} yaks", black_box(2));
+        });
+
+        logs.assert_record(0)
+            .level(Level::Fatal)
+            .target(module_path!())
+            .message("shaving yak")
+            .safe_param("attempt", 3)
+            .safe_param("service", "yak-shaver")
+            .unsafe_param("yak", "bob")
+            .no_unsafe_param("message");
+        assert_eq!(logs.records()[0].file(), Some(file!()));
+
+        logs.assert_record(1)
+            .level(Level::Debug)
+            .message("")
+            .safe_param("service", "yak-shaver")
+            .unsafe_param("yak", "alice")
+            .unsafe_param("message", "shaved 2 yaks");
+    }
+}
@@ -12,45 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use crate::bridge::{self, BridgedLogger};
-use crate::{Level, LevelFilter, Log, Metadata, Record};
+use crate::testing::{self, Capture};
+use crate::{mdc, Backtraced, Level, LevelFilter, OwnedRecord};
 use conjure_error::Error;
+use conjure_object::{Any, DateTime, Utc};
 use serde_value::Value;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::hint::black_box;
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+use std::{error, fmt, io};
 
+// The global logger can only be set once per process, so these tests record through the testing module's logger
+// rather than a separate one. Otherwise the testing module's own tests would race these to install theirs.
 thread_local! {
-    static RECORDS: RefCell<Vec<TestRecord>> = const { RefCell::new(vec![]) };
-}
-
-struct TestLogger;
-
-impl Log for TestLogger {
-    fn enabled(&self, _: &Metadata<'_>) -> bool {
-        true
-    }
-
-    fn log(&self, record: &Record<'_>) {
-        let record = TestRecord {
-            level: record.level(),
-            target: record.target().to_string(),
-            file: record.file().map(|s| s.to_string()),
-            line: record.line(),
-            message: record.message(),
-            safe_params: record
-                .safe_params()
-                .iter()
-                .map(|(k, v)| (*k, serde_value::to_value(v).unwrap()))
-                .collect(),
-            unsafe_params: record
-                .unsafe_params()
-                .iter()
-                .map(|(k, v)| (*k, serde_value::to_value(v).unwrap()))
-                .collect(),
-            error: record.error().map(|e| e.cause().to_string()),
-        };
-        RECORDS.with(|r| r.borrow_mut().push(record));
-    }
-
-    fn flush(&self) {}
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
 }
 
 struct TestRecord {
@@ -58,20 +34,64 @@ struct TestRecord {
     target: String,
     file: Option<String>,
     line: Option<u32>,
-    message: &'static str,
+    message: String,
     safe_params: Vec<(&'static str, Value)>,
     unsafe_params: Vec<(&'static str, Value)>,
     error: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+    thread_name: Option<String>,
+    thread_id: Option<ThreadId>,
+    mdc: Option<mdc::Snapshot>,
+    owned: Option<OwnedRecord>,
 }
 
 fn init() {
-    let _ = crate::set_logger(&TestLogger);
+    CAPTURE.with(|c| {
+        let mut capture = c.borrow_mut();
+        *capture = None;
+        *capture = Some(Capture::start());
+    });
     crate::set_max_level(LevelFilter::Trace);
-    RECORDS.with(|r| r.borrow_mut().clear());
+}
+
+// parameter keys are leaked so that they can be compared against literals in assertions
+fn leak(s: &str) -> &'static str {
+    Box::leak(s.into())
 }
 
 fn get_records() -> Vec<TestRecord> {
-    RECORDS.with(|r| r.replace(vec![]))
+    let logs = CAPTURE.with(|c| {
+        let mut capture = c.borrow_mut();
+        let logs = capture.take().unwrap().finish();
+        *capture = Some(Capture::start());
+        logs
+    });
+
+    logs.into_iter()
+        .map(|record| TestRecord {
+            level: record.level(),
+            target: record.target().to_string(),
+            file: record.file().map(|s| s.to_string()),
+            line: record.line(),
+            message: record.message().to_string(),
+            safe_params: record
+                .safe_params()
+                .iter()
+                .map(|(k, v)| (leak(k), serde_value::to_value(v).unwrap()))
+                .collect(),
+            unsafe_params: record
+                .unsafe_params()
+                .iter()
+                .map(|(k, v)| (leak(k), serde_value::to_value(v).unwrap()))
+                .collect(),
+            error: record.error().map(|e| e.causes()[0].clone()),
+            timestamp: record.timestamp(),
+            thread_name: record.thread_name().map(|s| s.to_string()),
+            thread_id: record.thread_id(),
+            mdc: record.mdc().cloned(),
+            owned: Some(record),
+        })
+        .collect()
 }
 
 #[test]
@@ -93,6 +113,8 @@ fn minimal() {
     assert_eq!(records[0].error, None);
 }
 
+// logs strings as safe parameters
+#[cfg(not(feature = "strict_safety"))]
 #[test]
 fn params() {
     init();
@@ -136,6 +158,141 @@ fn errors() {
     assert_eq!(records[0].error.as_ref().unwrap(), "error message");
 }
 
+#[test]
+fn std_errors() {
+    let io_error = io::Error::other("io error");
+    let boxed: Box<dyn error::Error + Sync + Send> = "boxed error".into();
+    let boxed_local: Box<dyn error::Error> = "boxed local error".into();
+    let boxed_conjure = Box::new(Error::internal_safe("boxed conjure error"));
+    let logs = testing::capture(|| {
+        warn!("io", error: io_error);
+        warn!("unboxed", error: *boxed);
+        warn!("boxed", error: boxed);
+        warn!("boxed local", error: boxed_local);
+        warn!("boxed conjure", error: boxed_conjure);
+        warn!("backtraced", error: Backtraced::new(io_error));
+    });
+
+    logs.assert_logged("io").error_cause("io error");
+    logs.assert_logged("unboxed").error_cause("boxed error");
+    logs.assert_logged("boxed").error_cause("boxed error");
+    logs.assert_logged("boxed local")
+        .error_cause("boxed local error");
+    let error = logs
+        .assert_logged("boxed conjure")
+        .record()
+        .error()
+        .unwrap();
+    assert_eq!(error.causes(), ["boxed conjure error"]);
+    assert!(error.cause_safe());
+    let error = logs.assert_logged("backtraced").record().error().unwrap();
+    assert_eq!(error.causes(), ["io error"]);
+    assert!(!error.cause_safe());
+}
+
+#[test]
+fn context() {
+    init();
+
+    let _guard = mdc::scope();
+    mdc::insert_safe("foo", "bar");
+    let start = Utc::now();
+    info!("message");
+    warn!("message", safe: { safe_param: 1 });
+    let end = Utc::now();
+    let records = get_records();
+    assert_eq!(records.len(), 2);
+
+    for record in &records {
+        let timestamp = record.timestamp.unwrap();
+        assert!(start <= timestamp && timestamp <= end);
+        assert_eq!(record.thread_name.as_deref(), thread::current().name());
+        assert_eq!(record.thread_id, Some(thread::current().id()));
+        assert_eq!(record.mdc.as_ref().unwrap(), &mdc::snapshot());
+    }
+}
+
+// logs strings as safe parameters
+#[cfg(not(feature = "strict_safety"))]
+#[test]
+fn owned() {
+    init();
+
+    let _guard = mdc::scope();
+    mdc::insert_safe("foo", "bar");
+    warn!(
+        "message",
+        safe: { safe_param: "foobar" },
+        unsafe: { unsafe_param: 15 },
+        error: Error::internal_safe("error message").with_safe_param("error_param", 1),
+    );
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    let owned = records[0].owned.clone().unwrap();
+    assert_eq!(owned.level(), Level::Warn);
+    assert_eq!(owned.message(), "message");
+    assert_eq!(
+        owned.safe_params(),
+        &[("safe_param".to_string(), Any::new("foobar").unwrap())],
+    );
+    assert_eq!(
+        owned.unsafe_params(),
+        &[("unsafe_param".to_string(), Any::new(15).unwrap())]
+    );
+    let error = owned.error().unwrap();
+    assert_eq!(error.causes(), &["error message".to_string()]);
+    assert!(error.cause_safe());
+    assert!(error.service_error().is_some());
+    assert_eq!(error.safe_params()["error_param"], Any::new(1).unwrap());
+    assert_eq!(error.backtraces().len(), 1);
+    assert_eq!(owned.mdc(), records[0].mdc.as_ref());
+
+    owned.as_record(|record| crate::logger().log(record));
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].level, Level::Warn);
+    assert_eq!(records[0].message, "message");
+    assert_eq!(
+        records[0].safe_params,
+        &[("safe_param", Value::String("foobar".to_string()))],
+    );
+    assert_eq!(records[0].owned.as_ref(), Some(&owned));
+
+    let value = serde_value::to_value(&owned).unwrap();
+    let deserialized = value.clone().deserialize_into::<OwnedRecord>().unwrap();
+    assert_eq!(deserialized.thread_id(), None);
+    assert_eq!(deserialized.error(), owned.error());
+    assert_eq!(serde_value::to_value(&deserialized).unwrap(), value);
+}
+
+#[test]
+fn owned_unserializable() {
+    struct Unserializable;
+
+    impl serde::Serialize for Unserializable {
+        fn serialize<S>(&self, _: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            Err(serde::ser::Error::custom("unserializable"))
+        }
+    }
+
+    init();
+
+    warn!("message", safe: { good: 1 }, unsafe: { bad: Unserializable });
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    let owned = records[0].owned.clone().unwrap();
+    assert_eq!(
+        owned.safe_params(),
+        &[("good".to_string(), Any::new(1).unwrap())]
+    );
+    assert_eq!(owned.unsafe_params(), &[]);
+}
+
 #[test]
 fn bridge() {
     init();
@@ -172,4 +329,329 @@ fn bridge() {
     assert_eq!(records[0].safe_params, &[]);
     assert_eq!(records[0].unsafe_params, &[]);
     assert_eq!(records[0].error, None);
+
+    log::info!(attempt = 3, user = "bob"; "shaving yak");
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    assert_eq!(records[0].message, "shaving yak");
+    assert_eq!(records[0].safe_params, &[]);
+    assert_eq!(
+        records[0].unsafe_params,
+        &[
+            ("attempt", Value::I64(3)),
+            ("user", Value::String("bob".to_string())),
+        ],
+    );
+}
+
+#[test]
+fn configured_bridge() {
+    init();
+
+    // the logger isn't installed since the log crate's logger can only be set once per process
+    let logger = BridgedLogger::builder()
+        .safe_key("attempt")
+        .safe_key_prefix("safe_")
+        .trusted_target("trusted")
+        .remap_level("old", log::Level::Trace, Level::Debug)
+        .rename_target("old", "new")
+        .build();
+
+    fn log(
+        logger: &dyn log::Log,
+        target: &str,
+        level: log::Level,
+        args: fmt::Arguments<'_>,
+        key_values: &[(&str, log::kv::Value<'_>)],
+    ) {
+        logger.log(
+            &log::Record::builder()
+                .target(target)
+                .level(level)
+                .args(args)
+                .key_values(&key_values)
+                .build(),
+        );
+    }
+
+    // keys don't need to be static
+    let size_key = String::from("safe_size");
+    log(
+        &logger,
+        "foo",
+        log::Level::Info,
+        format_args!("shaving yak"),
+        &[
+            ("attempt", 3i64.into()),
+            (&size_key, "large".into()),
+            ("user", "bob".into()),
+        ],
+    );
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    assert_eq!(records[0].message, "shaving yak");
+    assert_eq!(
+        records[0].safe_params,
+        &[
+            ("attempt", Value::I64(3)),
+            ("size", Value::String("large".to_string())),
+        ],
+    );
+    assert_eq!(
+        records[0].unsafe_params,
+        &[("user", Value::String("bob".to_string()))],
+    );
+
+    log(
+        &logger,
+        "old::foo",
+        log::Level::Trace,
+        format_args!("remapped"),
+        &[],
+    );
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    assert_eq!(records[0].level, Level::Debug);
+    assert_eq!(records[0].target, "new::foo");
+
+    log(
+        &logger,
+        "trusted",
+        log::Level::Info,
+        format_args!("shaved {} yaks", black_box(123)),
+        &[("user", "bob".into())],
+    );
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    assert_eq!(records[0].message, "");
+    assert_eq!(
+        records[0].safe_params,
+        &[
+            ("message", Value::String("shaved 123 yaks".to_string())),
+            ("user", Value::String("bob".to_string())),
+        ],
+    );
+    assert_eq!(records[0].unsafe_params, &[]);
+}
+
+#[test]
+fn rate_limited() {
+    init();
+
+    // the period is wide enough that scheduling jitter can't let a second record through; the timing itself is
+    // covered by the RateLimiter tests
+    for i in 0..3 {
+        warn!(every: Duration::from_secs(3600), "message", safe: { i: i });
+    }
+    let records = get_records();
+    assert_eq!(records.len(), 1);
+
+    assert_eq!(records[0].safe_params, &[("i", Value::I32(0))]);
+}
+
+#[test]
+fn once() {
+    init();
+
+    for i in 0..3 {
+        info_once!("message", safe: { i: i });
+        trace_once!("minimal");
+    }
+    let records = get_records();
+    assert_eq!(records.len(), 2);
+
+    assert_eq!(records[0].message, "message");
+    assert_eq!(records[0].safe_params, &[("i", Value::I32(0))]);
+    assert_eq!(records[1].message, "minimal");
+}
+
+#[test]
+fn capture() {
+    let logs = testing::capture(|| {
+        info!("outer", safe: { foo: 1 });
+        let inner = testing::capture(|| {
+            error!("inner", unsafe: { bar: "baz" }, error: Error::internal_safe("oops"));
+        });
+        inner
+            .assert_single()
+            .level(Level::Error)
+            .message("inner")
+            .no_safe_param("bar")
+            .unsafe_param("bar", "baz")
+            .error_cause("oops");
+        debug!("outer");
+    });
+
+    assert_eq!(logs.len(), 2);
+    logs.assert_record(0)
+        .level(Level::Info)
+        .target(module_path!())
+        .safe_param("foo", 1)
+        .no_error();
+    assert_eq!(
+        logs.assert_logged("outer").safe_param_value::<i32>("foo"),
+        1
+    );
+    logs.assert_record(1).level(Level::Debug);
+
+    testing::capture(|| {}).assert_empty();
+}
+
+#[test]
+fn interest_cache() {
+    init();
+
+    for i in 0..3 {
+        if enabled!(Level::Debug) {
+            debug!("message", safe: { i: i });
+        }
+        crate::rebuild_interest_cache();
+    }
+    let records = get_records();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[2].safe_params, &[("i", Value::I32(2))]);
+}
+
+#[test]
+fn lazy() {
+    init();
+
+    let calls = Cell::new(0);
+    let expensive = || {
+        calls.set(calls.get() + 1);
+        vec![1, 2, 3]
+    };
+    info!(
+        "message",
+        safe: { dump: lazy expensive, count: 3 },
+        unsafe: { user: lazy || "bob" },
+    );
+    let records = get_records();
+    assert_eq!(calls.get(), 1);
+    assert_eq!(
+        records[0].safe_params,
+        &[
+            (
+                "dump",
+                Value::Seq(vec![Value::I32(1), Value::I32(2), Value::I32(3)])
+            ),
+            ("count", Value::I32(3)),
+        ]
+    );
+    assert_eq!(
+        records[0].unsafe_params,
+        &[("user", Value::String("bob".to_string()))]
+    );
+
+    let unused = crate::private::Lazy::new(expensive);
+    drop(unused);
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn tags() {
+    let _guard = mdc::scope();
+    mdc::insert_tag("mdc", "value");
+
+    let stage = "startup".to_string();
+    let logs = testing::capture(|| {
+        info!("message", safe: { foo: 1 }, tags: { stage: stage, env: "test" });
+    });
+
+    let record = logs
+        .assert_single()
+        .tag("stage", "startup")
+        .tag("env", "test")
+        .record();
+    assert_eq!(record.mdc().unwrap().tags().get("mdc"), Some("value"));
+
+    let value = serde_value::to_value(record).unwrap();
+    let deserialized = value.deserialize_into::<OwnedRecord>().unwrap();
+    // serde_value sorts map entries
+    let mut tags = record.tags().to_vec();
+    tags.sort();
+    assert_eq!(deserialized.tags(), tags);
+    assert_eq!(deserialized.mdc(), record.mdc());
+}
+
+#[test]
+fn panic_hook() {
+    crate::panic::install_hook();
+
+    let mut line = 0;
+    let logs = testing::capture(|| {
+        line = line!() + 1;
+        let _ = std::panic::catch_unwind(|| panic!("static"));
+        let value = std::hint::black_box(1);
+        let _ = std::panic::catch_unwind(|| panic!("dynamic {value}"));
+    });
+
+    let static_panic = logs
+        .assert_record(0)
+        .level(Level::Fatal)
+        .message("panic")
+        .safe_param("payload", "static")
+        .error_cause("static")
+        .record();
+    assert_eq!(static_panic.file(), Some(file!()));
+    assert_eq!(static_panic.line(), Some(line));
+    assert!(!static_panic.error().unwrap().cause_safe());
+    assert!(!static_panic.error().unwrap().backtraces().is_empty());
+
+    logs.assert_record(1)
+        .no_safe_param("payload")
+        .unsafe_param("payload", "dynamic 1")
+        .error_cause("dynamic 1");
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive_params() {
+    use crate::LogParams;
+
+    #[derive(LogParams)]
+    struct Request<T> {
+        #[safe]
+        attempt: u32,
+        #[r#unsafe]
+        user: T,
+        path: &'static str,
+        #[skip]
+        #[allow(dead_code)]
+        body: Vec<u8>,
+    }
+
+    let request = Request {
+        attempt: 2,
+        user: "bob",
+        path: "/yaks",
+        body: vec![],
+    };
+    let logs = testing::capture(|| {
+        info!(
+            "message",
+            safe: { foo: 1 },
+            unsafe: { bar: 2 },
+            params: request,
+            tags: { stage: "test" },
+        );
+        warn!(every: Duration::from_millis(100), "rate limited", params: &request);
+    });
+
+    logs.assert_record(0)
+        .safe_param("foo", 1)
+        .safe_param("attempt", 2u32)
+        .unsafe_param("bar", 2)
+        .unsafe_param("user", "bob")
+        .unsafe_param("path", "/yaks")
+        .no_safe_param("body")
+        .no_unsafe_param("body")
+        .tag("stage", "test");
+    logs.assert_record(1)
+        .message("rate limited")
+        .safe_param("attempt", 2u32);
 }
@@ -0,0 +1,436 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Utilities for testing code which logs.
+//!
+//! This module is only available with the `testing` Cargo feature enabled. It provides a global logger which captures
+//! records logged on the current thread, along with helpers to make assertions about them.
+//!
+//! Capturing installs the module's logger as the global logger the first time it is used, and panics if a different
+//! logger has already been installed. Installation also sets the max log level to `Trace`; later captures leave the
+//! max level alone, so tests which adjust it with [`set_max_level`](crate::set_max_level) keep their setting. Only
+//! records logged on the capturing thread are captured.
+//!
+//! # Examples
+//!
+//! ```
+//! use witchcraft_log::testing;
+//! use witchcraft_log::Level;
+//!
+//! let logs = testing::capture(|| {
+//!     witchcraft_log::warn!("failed to shave yak", safe: { attempt: 3 }, unsafe: { yak: "bob" });
+//! });
+//!
+//! logs.assert_single()
+//!     .level(Level::Warn)
+//!     .message("failed to shave yak")
+//!     .safe_param("attempt", 3)
+//!     .unsafe_param("yak", "bob")
+//!     .no_error();
+//! ```
+use crate::{Level, LevelFilter, Log, Metadata, OwnedRecord, Record};
+use conjure_object::Any;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+use std::{mem, slice, vec};
+
+thread_local! {
+    static CAPTURES: RefCell<Vec<Vec<OwnedRecord>>> = const { RefCell::new(vec![]) };
+}
+
+static INSTALLED: OnceLock<bool> = OnceLock::new();
+
+struct CaptureLogger;
+
+impl Log for CaptureLogger {
+    // this can't depend on the thread local state since the result is cached per call site
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        let _ = CAPTURES.try_with(|c| {
+            if let Some(records) = c.borrow_mut().last_mut() {
+                records.push(record.to_owned());
+            }
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+fn install() {
+    let installed = *INSTALLED.get_or_init(|| {
+        let installed = crate::set_logger(&CaptureLogger).is_ok();
+        if installed {
+            crate::set_max_level(LevelFilter::Trace);
+        }
+        installed
+    });
+    assert!(installed, "a different logger has already been installed");
+}
+
+/// Captures all records logged on the current thread while the closure runs.
+///
+/// # Panics
+///
+/// Panics if a logger other than this module's has already been installed.
+#[track_caller]
+pub fn capture<F>(f: F) -> Logs
+where
+    F: FnOnce(),
+{
+    let capture = Capture::start();
+    f();
+    capture.finish()
+}
+
+/// A guard which captures all records logged on the current thread until it is finished or dropped.
+///
+/// Captures can be nested, in which case records are only captured by the innermost capture.
+pub struct Capture {
+    depth: usize,
+    // the capture is tied to the current thread
+    _p: PhantomData<*const ()>,
+}
+
+impl Capture {
+    /// Begins capturing records logged on the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a logger other than this module's has already been installed.
+    #[track_caller]
+    pub fn start() -> Capture {
+        install();
+        let depth = CAPTURES.with(|c| {
+            let mut captures = c.borrow_mut();
+            captures.push(vec![]);
+            captures.len()
+        });
+
+        Capture {
+            depth,
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns the records captured so far.
+    pub fn logs(&self) -> Logs {
+        CAPTURES.with(|c| Logs {
+            records: c.borrow()[self.depth - 1].clone(),
+        })
+    }
+
+    /// Stops capturing, returning the captured records.
+    pub fn finish(self) -> Logs {
+        CAPTURES.with(|c| Logs {
+            records: mem::take(&mut c.borrow_mut()[self.depth - 1]),
+        })
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        // the capture may be dropped during thread local destruction
+        let _ = CAPTURES.try_with(|c| c.borrow_mut().truncate(self.depth - 1));
+    }
+}
+
+/// A collection of captured records.
+#[derive(Clone, Debug, Default)]
+pub struct Logs {
+    records: Vec<OwnedRecord>,
+}
+
+impl Logs {
+    /// Returns the captured records in the order they were logged.
+    #[inline]
+    pub fn records(&self) -> &[OwnedRecord] {
+        &self.records
+    }
+
+    /// Returns the number of captured records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Determines if no records were captured.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns an iterator over the captured records.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, OwnedRecord> {
+        self.records.iter()
+    }
+
+    /// Asserts that no records were captured.
+    #[track_caller]
+    pub fn assert_empty(&self) {
+        assert!(
+            self.records.is_empty(),
+            "expected no records but got {:#?}",
+            self.records,
+        );
+    }
+
+    /// Asserts that exactly one record was captured, returning it.
+    #[track_caller]
+    pub fn assert_single(&self) -> RecordAssert<'_> {
+        assert_eq!(
+            self.records.len(),
+            1,
+            "expected a single record but got {:#?}",
+            self.records,
+        );
+        RecordAssert::new(&self.records[0])
+    }
+
+    /// Asserts that at least `index + 1` records were captured, returning the record at the specified index.
+    #[track_caller]
+    pub fn assert_record(&self, index: usize) -> RecordAssert<'_> {
+        match self.records.get(index) {
+            Some(record) => RecordAssert::new(record),
+            None => panic!(
+                "expected a record at index {index} but got {:#?}",
+                self.records
+            ),
+        }
+    }
+
+    /// Asserts that a record with the specified message was captured, returning the first one.
+    #[track_caller]
+    pub fn assert_logged(&self, message: &str) -> RecordAssert<'_> {
+        match self.records.iter().find(|r| r.message() == message) {
+            Some(record) => RecordAssert::new(record),
+            None => panic!(
+                "expected a record with message `{message}` but got {:#?}",
+                self.records
+            ),
+        }
+    }
+}
+
+impl IntoIterator for Logs {
+    type Item = OwnedRecord;
+
+    type IntoIter = vec::IntoIter<OwnedRecord>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.records.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Logs {
+    type Item = &'a OwnedRecord;
+
+    type IntoIter = slice::Iter<'a, OwnedRecord>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A helper to make assertions about a captured record.
+///
+/// All methods panic with a description of the record if the assertion fails.
+#[derive(Copy, Clone)]
+pub struct RecordAssert<'a> {
+    record: &'a OwnedRecord,
+}
+
+impl<'a> RecordAssert<'a> {
+    fn new(record: &'a OwnedRecord) -> Self {
+        RecordAssert { record }
+    }
+
+    /// Returns the record.
+    #[inline]
+    pub fn record(self) -> &'a OwnedRecord {
+        self.record
+    }
+
+    /// Asserts that the record has the specified level.
+    #[track_caller]
+    pub fn level(self, level: Level) -> Self {
+        assert_eq!(self.record.level(), level, "{:#?}", self.record);
+        self
+    }
+
+    /// Asserts that the record has the specified target.
+    #[track_caller]
+    pub fn target(self, target: &str) -> Self {
+        assert_eq!(self.record.target(), target, "{:#?}", self.record);
+        self
+    }
+
+    /// Asserts that the record has the specified message.
+    #[track_caller]
+    pub fn message(self, message: &str) -> Self {
+        assert_eq!(self.record.message(), message, "{:#?}", self.record);
+        self
+    }
+
+    /// Asserts that the record has a safe parameter with the specified value.
+    #[track_caller]
+    pub fn safe_param<T>(self, key: &str, value: T) -> Self
+    where
+        T: Serialize,
+    {
+        assert_param(self.record, "safe", self.record.safe_params(), key, value);
+        self
+    }
+
+    /// Asserts that the record has an unsafe parameter with the specified value.
+    #[track_caller]
+    pub fn unsafe_param<T>(self, key: &str, value: T) -> Self
+    where
+        T: Serialize,
+    {
+        assert_param(
+            self.record,
+            "unsafe",
+            self.record.unsafe_params(),
+            key,
+            value,
+        );
+        self
+    }
+
+    /// Asserts that the record does not have a safe parameter with the specified key.
+    #[track_caller]
+    pub fn no_safe_param(self, key: &str) -> Self {
+        assert!(
+            find_param(self.record.safe_params(), key).is_none(),
+            "expected no safe param `{key}` in {:#?}",
+            self.record,
+        );
+        self
+    }
+
+    /// Asserts that the record does not have an unsafe parameter with the specified key.
+    #[track_caller]
+    pub fn no_unsafe_param(self, key: &str) -> Self {
+        assert!(
+            find_param(self.record.unsafe_params(), key).is_none(),
+            "expected no unsafe param `{key}` in {:#?}",
+            self.record,
+        );
+        self
+    }
+
+    /// Asserts that the record has a safe parameter with the specified key, returning its decoded value.
+    #[track_caller]
+    pub fn safe_param_value<T>(self, key: &str) -> T
+    where
+        T: DeserializeOwned,
+    {
+        decode_param(self.record, "safe", self.record.safe_params(), key)
+    }
+
+    /// Asserts that the record has an unsafe parameter with the specified key, returning its decoded value.
+    #[track_caller]
+    pub fn unsafe_param_value<T>(self, key: &str) -> T
+    where
+        T: DeserializeOwned,
+    {
+        decode_param(self.record, "unsafe", self.record.unsafe_params(), key)
+    }
+
+    /// Asserts that the record has a tag with the specified value.
+    #[track_caller]
+    pub fn tag(self, key: &str, value: &str) -> Self {
+        match self.record.tags().iter().find(|(k, _)| *k == key) {
+            Some((_, actual)) => assert_eq!(
+                actual, value,
+                "unexpected value for tag `{key}` in {:#?}",
+                self.record,
+            ),
+            None => panic!("expected tag `{key}` in {:#?}", self.record),
+        }
+        self
+    }
+
+    /// Asserts that the record has an error whose cause chain contains the specified message.
+    #[track_caller]
+    pub fn error_cause(self, cause: &str) -> Self {
+        let error = match self.record.error() {
+            Some(error) => error,
+            None => panic!("expected an error in {:#?}", self.record),
+        };
+        assert!(
+            error.causes().iter().any(|c| c == cause),
+            "expected error cause `{cause}` in {:#?}",
+            self.record,
+        );
+        self
+    }
+
+    /// Asserts that the record does not have an error.
+    #[track_caller]
+    pub fn no_error(self) -> Self {
+        assert!(
+            self.record.error().is_none(),
+            "expected no error in {:#?}",
+            self.record,
+        );
+        self
+    }
+}
+
+fn find_param<'a>(params: &'a [(String, Any)], key: &str) -> Option<&'a Any> {
+    params.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+}
+
+#[track_caller]
+fn assert_param<T>(
+    record: &OwnedRecord,
+    safety: &str,
+    params: &[(String, Any)],
+    key: &str,
+    value: T,
+) where
+    T: Serialize,
+{
+    let expected = Any::new(value).expect("value failed to serialize");
+    match find_param(params, key) {
+        Some(actual) => assert_eq!(
+            actual, &expected,
+            "unexpected value for {safety} param `{key}` in {record:#?}",
+        ),
+        None => panic!("expected {safety} param `{key}` in {record:#?}"),
+    }
+}
+
+#[track_caller]
+fn decode_param<T>(record: &OwnedRecord, safety: &str, params: &[(String, Any)], key: &str) -> T
+where
+    T: DeserializeOwned,
+{
+    match find_param(params, key) {
+        Some(value) => match value.clone().deserialize_into() {
+            Ok(value) => value,
+            Err(e) => panic!("error decoding {safety} param `{key}`: {e}"),
+        },
+        None => panic!("expected {safety} param `{key}` in {record:#?}"),
+    }
+}
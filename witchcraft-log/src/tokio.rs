@@ -0,0 +1,160 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for task local MDCs in `tokio` runtimes.
+//!
+//! This module is only available with the `tokio` Cargo feature enabled. The MDC is normally thread local, so tasks
+//! spawned onto a multithreaded runtime must each be wrapped in [`mdc::bind`] to keep their MDC separate from the other
+//! tasks running on the same worker threads. This module instead stores the MDC in a `tokio` task local. Futures
+//! wrapped with [`bind`] run with their own MDC, which all of the functions in the [`mdc`] module use in place of the
+//! thread local MDC. Outside of those futures, the thread local MDC is used as before.
+//!
+//! Tasks spawned with [`spawn`] automatically start with a copy of the spawning task's MDC, and closures run with
+//! [`spawn_blocking`] do the same. Updates made to the MDC by a child task are not visible to its parent.
+//!
+//! # Examples
+//!
+//! ```
+//! use witchcraft_log::{mdc, tokio};
+//!
+//! # let runtime = ::tokio::runtime::Builder::new_current_thread().build().unwrap();
+//! # runtime.block_on(async {
+//! tokio::spawn(async {
+//!     mdc::insert_safe("requestId", "abc");
+//!
+//!     // the child task logs with the requestId parameter
+//!     tokio::spawn(async { witchcraft_log::info!("shaving yak") }).await.unwrap();
+//! })
+//! .await
+//! .unwrap();
+//! # });
+//! ```
+use crate::mdc::{self, Snapshot, TASK_MDC};
+use pin_project::pin_project;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::task::futures::TaskLocalFuture;
+use tokio::task::JoinHandle;
+
+/// Wraps a future so that it runs with its own task local MDC.
+///
+/// The MDC of the future starts as a copy of the current MDC.
+pub fn bind<F>(future: F) -> Bind<F>
+where
+    F: Future,
+{
+    Bind {
+        future: TASK_MDC.scope(RefCell::new(mdc::snapshot()), future),
+    }
+}
+
+/// Spawns a new task with its own task local MDC.
+///
+/// The MDC of the task starts as a copy of the current MDC. This is equivalent to
+/// `tokio::spawn(witchcraft_log::tokio::bind(future))`.
+///
+/// # Panics
+///
+/// Panics if called outside of a `tokio` runtime.
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(bind(future))
+}
+
+/// Runs a closure on the `tokio` blocking thread pool with a copy of the current MDC.
+///
+/// This is equivalent to `tokio::task::spawn_blocking(witchcraft_log::mdc::bind_fn(f))`.
+///
+/// # Panics
+///
+/// Panics if called outside of a `tokio` runtime.
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    tokio::task::spawn_blocking(mdc::bind_fn(f))
+}
+
+/// A future which runs a delegate with its own task local MDC.
+#[pin_project]
+pub struct Bind<F>
+where
+    F: Future,
+{
+    #[pin]
+    future: TaskLocalFuture<RefCell<Snapshot>, F>,
+}
+
+impl<F> Future for Bind<F>
+where
+    F: Future,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().future.poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{mdc, tokio};
+    use conjure_object::Any;
+
+    fn foo() -> Option<Any> {
+        mdc::snapshot().safe().get("foo").cloned()
+    }
+
+    #[test]
+    fn task_local() {
+        mdc::clear();
+        mdc::insert_safe("foo", "bar");
+
+        let runtime = ::tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(tokio::bind(async {
+            assert_eq!(foo(), Some(Any::new("bar").unwrap()));
+            mdc::insert_safe("foo", "baz");
+
+            let child = tokio::spawn(async {
+                let inherited = foo();
+                mdc::insert_safe("foo", "qux");
+                ::tokio::task::yield_now().await;
+                (inherited, foo())
+            });
+            // unbound tasks fall back to the thread local MDC
+            let unbound = ::tokio::spawn(async { foo() });
+            let blocking = tokio::spawn_blocking(foo);
+
+            assert_eq!(
+                child.await.unwrap(),
+                (
+                    Some(Any::new("baz").unwrap()),
+                    Some(Any::new("qux").unwrap())
+                ),
+            );
+            assert_eq!(unbound.await.unwrap(), Some(Any::new("bar").unwrap()));
+            assert_eq!(blocking.await.unwrap(), Some(Any::new("baz").unwrap()));
+            assert_eq!(foo(), Some(Any::new("baz").unwrap()));
+        }));
+
+        assert_eq!(foo(), Some(Any::new("bar").unwrap()));
+    }
+}
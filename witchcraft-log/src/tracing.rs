@@ -0,0 +1,528 @@
+// Copyright 2026 Palantir Technologies, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Support for forwarding events from the `tracing` crate to `witchcraft-log`.
+//!
+//! This module is only available with the `tracing` Cargo feature enabled. It provides a [`WitchcraftLayer`] which
+//! converts `tracing` events into `witchcraft-log` records and mirrors the fields of entered spans into the MDC.
+//!
+//! # Messages
+//!
+//! **Only events with an explicit `name:` are logged with a message.** An unnamed event like
+//! `tracing::info!("request finished")` produces a record with an empty message, and its text is instead included as
+//! the unsafe `message` parameter.
+//!
+//! `witchcraft-log` messages must be static, but `tracing` hands an event's message to the layer as a type-erased
+//! `&dyn Debug` rather than as `fmt::Arguments`, so unlike records forwarded from the `log` crate, a static message
+//! like `"request finished"` cannot be told apart from a formatted one like `"{}"` wrapping sensitive data. An event's
+//! name is always static, so it is used as the record's message instead. `tracing` does not record whether a name was
+//! given explicitly, so events whose name matches the `event <file>:<line>` default `tracing` generates are treated
+//! as unnamed.
+//!
+//! # Safety
+//!
+//! Event and span fields are treated as unsafe parameters by default. A field is instead treated as a safe parameter
+//! if its name has been registered with [`Builder::safe_field`], or if its name starts with the safe prefix (`safe.`
+//! by default), in which case the prefix is removed from the parameter's name.
+//!
+//! The `message` parameter is unsafe like any other field unless `message` is registered with
+//! [`Builder::safe_field`].
+//!
+//! # Examples
+//!
+//! ```
+//! use tracing_subscriber::layer::SubscriberExt;
+//! use witchcraft_log::tracing::WitchcraftLayer;
+//!
+//! let layer = WitchcraftLayer::builder().safe_field("status").build();
+//! tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer)).unwrap();
+//!
+//! tracing::info!(name: "request finished", status = 200, safe.duration_ms = 15, user = "bob");
+//! ```
+use crate::{mdc, Level, Metadata, Record};
+use conjure_object::{Any, Utc};
+use erased_serde::Serialize;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::thread;
+use tracing_core::field::{Field, Visit};
+use tracing_core::span::{Attributes, Id};
+use tracing_core::{span, Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+thread_local! {
+    static ENTERED: RefCell<Vec<(Id, Vec<Previous>)>> = const { RefCell::new(vec![]) };
+}
+
+// the value a span's field replaced in the MDC when the span was entered
+struct Previous {
+    key: &'static str,
+    safe: bool,
+    value: Option<Any>,
+}
+
+fn cvt_level(level: tracing_core::Level) -> Level {
+    match level {
+        tracing_core::Level::ERROR => Level::Error,
+        tracing_core::Level::WARN => Level::Warn,
+        tracing_core::Level::INFO => Level::Info,
+        tracing_core::Level::DEBUG => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+// tracing doesn't expose whether a name was explicit, but events without one are named "event {file}:{line}"
+fn explicit_name(metadata: &'static tracing_core::Metadata<'static>) -> Option<&'static str> {
+    let name = metadata.name();
+    let default = match (metadata.file(), metadata.line()) {
+        (Some(file), Some(line)) => {
+            name.strip_prefix("event ")
+                .and_then(|n| n.strip_prefix(file))
+                .and_then(|n| n.strip_prefix(':'))
+                .and_then(|n| n.parse::<u32>().ok())
+                == Some(line)
+        }
+        _ => false,
+    };
+
+    if default {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+/// A `tracing_subscriber::Layer` that forwards events to the `witchcraft-log` logger.
+///
+/// Records are only given a message if the event has an explicit `name:`. See the [module docs](self) for details.
+pub struct WitchcraftLayer {
+    safe_fields: HashSet<String>,
+    safe_prefix: Option<String>,
+}
+
+impl Default for WitchcraftLayer {
+    fn default() -> Self {
+        WitchcraftLayer::new()
+    }
+}
+
+impl WitchcraftLayer {
+    /// Creates a new layer with the default configuration.
+    pub fn new() -> Self {
+        WitchcraftLayer::builder().build()
+    }
+
+    /// Returns a builder used to configure a layer.
+    pub fn builder() -> Builder {
+        Builder {
+            safe_fields: HashSet::new(),
+            safe_prefix: Some("safe.".to_string()),
+        }
+    }
+
+    fn safe_key(&self, name: &'static str) -> Option<&'static str> {
+        if self.safe_fields.contains(name) {
+            return Some(name);
+        }
+
+        self.safe_prefix
+            .as_ref()
+            .and_then(|prefix| name.strip_prefix(&**prefix))
+            .filter(|key| !key.is_empty())
+    }
+
+    fn visitor<'a>(&'a self, fields: &'a mut Fields) -> Visitor<'a> {
+        Visitor {
+            layer: self,
+            fields,
+        }
+    }
+}
+
+impl<S> Layer<S> for WitchcraftLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut fields = Fields::default();
+        attrs.record(&mut self.visitor(&mut fields));
+        span.extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<Fields>() {
+            values.record(&mut self.visitor(fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = cvt_level(*metadata.level());
+        if level > crate::max_level()
+            || !crate::logger().enabled(
+                &Metadata::builder()
+                    .level(level)
+                    .target(metadata.target())
+                    .build(),
+            )
+        {
+            return;
+        }
+
+        let mut fields = Fields::default();
+        event.record(&mut self.visitor(&mut fields));
+        let safe_params = fields.safe_params();
+        let unsafe_params = fields.unsafe_params();
+
+        let thread = thread::current();
+        let mdc = mdc::snapshot();
+
+        crate::logger().log(
+            &Record::builder()
+                .level(level)
+                .target(metadata.target())
+                .file(metadata.file())
+                .line(metadata.line())
+                .message(explicit_name(metadata).unwrap_or(""))
+                .safe_params(&safe_params)
+                .unsafe_params(&unsafe_params)
+                .timestamp(Some(Utc::now()))
+                .thread_name(thread.name())
+                .thread_id(Some(thread.id()))
+                .mdc(Some(&mdc))
+                .build(),
+        );
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut previous = vec![];
+        if let Some(fields) = span.extensions().get::<Fields>() {
+            for (key, value) in &fields.safe {
+                previous.push(Previous {
+                    key,
+                    safe: true,
+                    value: mdc::insert_safe(key, value),
+                });
+            }
+            for (key, value) in &fields.unsafe_ {
+                previous.push(Previous {
+                    key,
+                    safe: false,
+                    value: mdc::insert_unsafe(key, value),
+                });
+            }
+        }
+
+        ENTERED.with(|e| e.borrow_mut().push((id.clone(), previous)));
+    }
+
+    fn on_exit(&self, id: &Id, _: Context<'_, S>) {
+        let previous = ENTERED.with(|e| {
+            let mut entered = e.borrow_mut();
+            entered
+                .iter()
+                .rposition(|(entered_id, _)| entered_id == id)
+                .map(|i| entered.remove(i).1)
+        });
+
+        // only the span's own keys are restored so that other changes made to the MDC while it was entered persist
+        for previous in previous.into_iter().flatten().rev() {
+            match (previous.safe, previous.value) {
+                (true, Some(value)) => {
+                    mdc::insert_safe(previous.key, value);
+                }
+                (true, None) => {
+                    mdc::remove_safe(previous.key);
+                }
+                (false, Some(value)) => {
+                    mdc::insert_unsafe(previous.key, value);
+                }
+                (false, None) => {
+                    mdc::remove_unsafe(previous.key);
+                }
+            }
+        }
+    }
+}
+
+/// A builder for [`WitchcraftLayer`].
+pub struct Builder {
+    safe_fields: HashSet<String>,
+    safe_prefix: Option<String>,
+}
+
+impl Builder {
+    /// Registers a field name which will be treated as a safe parameter.
+    pub fn safe_field(mut self, name: &str) -> Self {
+        self.safe_fields.insert(name.to_string());
+        self
+    }
+
+    /// Sets the prefix identifying fields which will be treated as safe parameters.
+    ///
+    /// The prefix is removed from the parameter's name. A value of `None` disables the naming convention.
+    ///
+    /// Defaults to `Some("safe.")`.
+    pub fn safe_prefix(mut self, prefix: Option<&str>) -> Self {
+        self.safe_prefix = prefix.map(|p| p.to_string());
+        self
+    }
+
+    /// Creates the layer.
+    pub fn build(self) -> WitchcraftLayer {
+        WitchcraftLayer {
+            safe_fields: self.safe_fields,
+            safe_prefix: self.safe_prefix,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Fields {
+    safe: Vec<(&'static str, Any)>,
+    unsafe_: Vec<(&'static str, Any)>,
+}
+
+impl Fields {
+    fn safe_params(&self) -> Vec<(&'static str, &dyn Serialize)> {
+        self.safe.iter().map(|(k, v)| (*k, v as _)).collect()
+    }
+
+    fn unsafe_params(&self) -> Vec<(&'static str, &dyn Serialize)> {
+        self.unsafe_.iter().map(|(k, v)| (*k, v as _)).collect()
+    }
+}
+
+struct Visitor<'a> {
+    layer: &'a WitchcraftLayer,
+    fields: &'a mut Fields,
+}
+
+impl Visitor<'_> {
+    fn record<T>(&mut self, field: &Field, value: T)
+    where
+        T: serde::Serialize,
+    {
+        let Ok(value) = Any::new(value) else {
+            return;
+        };
+
+        let (params, key) = match self.layer.safe_key(field.name()) {
+            Some(key) => (&mut self.fields.safe, key),
+            None => (&mut self.fields.unsafe_, field.name()),
+        };
+
+        match params.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, v)) => *v = value,
+            None => params.push((key, value)),
+        }
+    }
+}
+
+impl Visit for Visitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, value);
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, value);
+    }
+
+    fn record_i128(&mut self, field: &Field, value: i128) {
+        self.record(field, value);
+    }
+
+    fn record_u128(&mut self, field: &Field, value: u128) {
+        self.record(field, value);
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, value);
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value);
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn Error + 'static)) {
+        self.record(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{value:?}"));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mdc;
+    use crate::testing;
+    use crate::tracing::WitchcraftLayer;
+    use crate::Level;
+    use conjure_object::Any;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn with_layer<F>(layer: WitchcraftLayer, f: F)
+    where
+        F: FnOnce(),
+    {
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), f);
+    }
+
+    #[test]
+    fn events() {
+        let layer = WitchcraftLayer::builder().safe_field("status").build();
+        let logs = testing::capture(|| {
+            with_layer(layer, || {
+                let user = "bob";
+                tracing::warn!(
+                    status = 500,
+                    safe.attempt = 3,
+                    user,
+                    "request for {} failed",
+                    user
+                );
+                tracing::info!(name: "request finished", status = 200);
+            })
+        });
+
+        logs.assert_record(0)
+            .level(Level::Warn)
+            .target(module_path!())
+            .message("")
+            .safe_param("status", 500i64)
+            .safe_param("attempt", 3i64)
+            .no_safe_param("safe.attempt")
+            .unsafe_param("user", "bob")
+            .unsafe_param("message", "request for bob failed");
+        logs.assert_record(1)
+            .level(Level::Info)
+            .message("request finished")
+            .safe_param("status", 200i64);
+    }
+
+    #[test]
+    fn unnamed_events() {
+        let logs = testing::capture(|| {
+            with_layer(WitchcraftLayer::new(), || {
+                let secret = "hunter2";
+                tracing::info!("static message");
+                tracing::info!("{}", secret);
+            })
+        });
+
+        // a static message can't be distinguished from a formatted one, so neither is used as the record's message
+        logs.assert_record(0)
+            .message("")
+            .no_safe_param("message")
+            .unsafe_param("message", "static message");
+        logs.assert_record(1)
+            .message("")
+            .no_safe_param("message")
+            .unsafe_param("message", "hunter2");
+    }
+
+    #[test]
+    fn named_events() {
+        let logs = testing::capture(|| {
+            with_layer(WitchcraftLayer::new(), || {
+                tracing::info!(name: "static name", "static message");
+                tracing::info!(name: "other name", status = 200);
+            })
+        });
+
+        logs.assert_record(0)
+            .message("static name")
+            .unsafe_param("message", "static message");
+        logs.assert_record(1)
+            .message("other name")
+            .no_unsafe_param("message");
+    }
+
+    #[test]
+    fn safe_prefix() {
+        let layer = WitchcraftLayer::builder()
+            .safe_prefix(Some("s_"))
+            .safe_field("message")
+            .build();
+        let logs = testing::capture(|| {
+            with_layer(layer, || {
+                tracing::info!(s_count = 1, safe.other = 2, "static message");
+            })
+        });
+
+        logs.assert_single()
+            .message("")
+            .safe_param("count", 1i64)
+            .safe_param("message", "static message")
+            .unsafe_param("safe.other", 2i64);
+    }
+
+    #[test]
+    fn span_mdc() {
+        mdc::clear();
+        mdc::insert_safe("existing", true);
+        mdc::insert_unsafe("user", "alice");
+
+        let logs = testing::capture(|| {
+            with_layer(WitchcraftLayer::new(), || {
+                let span = tracing::info_span!("request", safe.request_id = "abc", user = "bob");
+                span.in_scope(|| {
+                    assert_eq!(
+                        mdc::snapshot().safe().get("request_id"),
+                        Some(&Any::new("abc").unwrap())
+                    );
+                    tracing::info!(name: "inside", count = 1);
+                    mdc::insert_safe("added", 1);
+                });
+                tracing::info!(name: "outside", count = 2);
+            })
+        });
+
+        let inside = logs.assert_record(0).message("inside").record();
+        let mdc = inside.mdc().unwrap();
+        assert_eq!(
+            mdc.safe().get("request_id"),
+            Some(&Any::new("abc").unwrap())
+        );
+        assert_eq!(mdc.safe().get("existing"), Some(&Any::new(true).unwrap()));
+        assert_eq!(mdc.unsafe_().get("user"), Some(&Any::new("bob").unwrap()));
+
+        let outside = logs.assert_record(1).message("outside").record();
+        let mdc = outside.mdc().unwrap();
+        assert_eq!(mdc.safe().get("request_id"), None);
+        assert_eq!(mdc.safe().get("existing"), Some(&Any::new(true).unwrap()));
+        assert_eq!(mdc.safe().get("added"), Some(&Any::new(1).unwrap()));
+        assert_eq!(mdc.unsafe_().get("user"), Some(&Any::new("alice").unwrap()));
+    }
+}